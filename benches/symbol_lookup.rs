@@ -0,0 +1,65 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use plt_rs::{collect_modules, DynamicLibrary, ModuleSnapshot, SymbolIndex};
+
+/// Every module of the bench process alongside the names of the symbols it relocates
+fn load_modules(snapshots: &[ModuleSnapshot]) -> Vec<(DynamicLibrary<'_>, Vec<String>)> {
+    snapshots
+        .iter()
+        .filter_map(|snapshot| snapshot.dynamic().ok())
+        .map(|dyn_lib| {
+            let mut names = dyn_lib
+                .build_index()
+                .entries()
+                .iter()
+                .map(|entry| entry.name.to_string_lossy().into_owned())
+                .collect::<Vec<_>>();
+            names.dedup();
+            (dyn_lib, names)
+        })
+        .collect()
+}
+
+/// Resolving every imported symbol of every module, the startup pattern of a hook-everything pass
+fn lookup_every_symbol(c: &mut Criterion) {
+    let snapshots = collect_modules();
+    let modules = load_modules(&snapshots);
+    let total = modules.iter().map(|(_, names)| names.len()).sum::<usize>();
+    println!("{} modules, {total} symbols", modules.len());
+
+    let mut group = c.benchmark_group("lookup_every_symbol");
+    group.bench_function("linear_scan", |b| {
+        b.iter(|| {
+            for (dyn_lib, names) in modules.iter() {
+                for name in names {
+                    black_box(dyn_lib.find_relocations(name).count());
+                }
+            }
+        })
+    });
+
+    group.bench_function("index_build", |b| {
+        b.iter(|| {
+            for (dyn_lib, _) in modules.iter() {
+                black_box(SymbolIndex::build(dyn_lib));
+            }
+        })
+    });
+
+    let indexes = modules
+        .iter()
+        .map(|(dyn_lib, names)| (dyn_lib.build_index(), names))
+        .collect::<Vec<_>>();
+    group.bench_function("index_lookup", |b| {
+        b.iter(|| {
+            for (index, names) in indexes.iter() {
+                for name in names.iter() {
+                    black_box(index.find(name).len());
+                }
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup_every_symbol);
+criterion_main!(benches);
@@ -1,15 +1,15 @@
 use anyhow::Result;
-use plt_rs::{DynamicLibrary, RelocationTable};
+use plt_rs::RelocationTable;
 
 fn main() -> Result<()> {
     let entries = plt_rs::collect_modules();
     println!("collected {} modules", entries.len());
 
-    for entry in entries.into_iter() {
-        let entry_name = entry.name().to_owned();
+    for entry in entries.iter() {
+        let entry_name = entry.name().into_owned();
         println!("[{}] Addr: {:#X?}", entry_name, entry.addr());
 
-        let Ok(dynamic_lib) = DynamicLibrary::initialize(entry) else {
+        let Ok(dynamic_lib) = entry.dynamic() else {
             println!(
                 "failed to parse {} as dynamic library, skipping...",
                 entry_name
@@ -66,6 +66,18 @@ fn main() -> Result<()> {
                 }
             }
         }
+
+        println!("ifuncs:");
+        for ifunc in dynamic_lib.ifunc_relocations() {
+            let name = ifunc.resolver_symbol.map(|s| s.to_string_lossy());
+            println!(
+                "\t{:#X} resolver {:#X} ({}) -> {:#X}",
+                ifunc.relocation.offset(),
+                ifunc.resolver.unwrap_or_default(),
+                name.as_deref().unwrap_or("?"),
+                ifunc.implementation
+            );
+        }
         println!();
     }
 
@@ -0,0 +1,98 @@
+use super::RelocationKind;
+
+// Entries DT_PLTGOT reserves for the dynamic linker, at the same indices on every architecture reserving them
+const GOT_DYNAMIC: usize = 0;
+const GOT_LINK_MAP: usize = 1;
+const GOT_RESOLVER: usize = 2;
+
+// Raw relocation type values differ per architecture, these are the subset plt-rs cares about.
+// https://refspecs.linuxfoundation.org/elf/
+#[cfg(target_arch = "x86_64")]
+mod constants {
+    pub const R_ABSOLUTE: u32 = 1; // R_X86_64_64
+    pub const R_COPY: u32 = 5;
+    pub const R_GLOB_DAT: u32 = 6;
+    pub const R_JUMP_SLOT: u32 = 7;
+    pub const R_RELATIVE: u32 = 8;
+    pub const R_IRELATIVE: u32 = 37;
+}
+
+#[cfg(target_arch = "x86")]
+mod constants {
+    pub const R_ABSOLUTE: u32 = 1; // R_386_32
+    pub const R_COPY: u32 = 5;
+    pub const R_GLOB_DAT: u32 = 6;
+    pub const R_JUMP_SLOT: u32 = 7;
+    pub const R_RELATIVE: u32 = 8;
+    pub const R_IRELATIVE: u32 = 42;
+}
+
+#[cfg(target_arch = "aarch64")]
+mod constants {
+    pub const R_ABSOLUTE: u32 = 257; // R_AARCH64_ABS64
+    pub const R_COPY: u32 = 1024;
+    pub const R_GLOB_DAT: u32 = 1025;
+    pub const R_JUMP_SLOT: u32 = 1026;
+    pub const R_RELATIVE: u32 = 1027;
+    pub const R_IRELATIVE: u32 = 1032;
+}
+
+#[cfg(target_arch = "arm")]
+mod constants {
+    pub const R_ABSOLUTE: u32 = 2; // R_ARM_ABS32
+    pub const R_COPY: u32 = 20;
+    pub const R_GLOB_DAT: u32 = 21;
+    pub const R_JUMP_SLOT: u32 = 22;
+    pub const R_RELATIVE: u32 = 23;
+    pub const R_IRELATIVE: u32 = 160;
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "aarch64",
+    target_arch = "arm"
+))]
+impl From<u32> for RelocationKind {
+    fn from(value: u32) -> Self {
+        use constants::*;
+        match value {
+            0 => Self::None,
+            R_ABSOLUTE => Self::Absolute,
+            R_COPY => Self::Copy,
+            R_GLOB_DAT => Self::GlobDat,
+            R_JUMP_SLOT => Self::JumpSlot,
+            R_RELATIVE => Self::Relative,
+            R_IRELATIVE => Self::IRelative,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Unsupported architectures only distinguish the universal R_*_NONE relocation
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "aarch64",
+    target_arch = "arm"
+)))]
+impl From<u32> for RelocationKind {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::None,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Indices of the GOT entries reserved for the dynamic linker: `_DYNAMIC`, the module's link_map and the lazy binding resolver.
+/// None on architectures whose reserved entries are not known.
+pub(crate) fn reserved_got_indices() -> Option<[usize; 3]> {
+    let reserves_header = cfg!(any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "aarch64",
+        target_arch = "arm"
+    ));
+    reserves_header.then_some([GOT_DYNAMIC, GOT_LINK_MAP, GOT_RESOLVER])
+}
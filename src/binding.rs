@@ -0,0 +1,196 @@
+use super::{write_protected, DynamicLibrary, PatchError, Relocation, RelocationKind};
+use core::error::Error;
+use core::ffi::{c_void, CStr};
+use core::fmt::Display;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Binding of weak symbols, which may stay undefined
+const STB_WEAK: u8 = 2;
+
+/// Errors binding a PLT slot ahead of the dynamic linker
+#[derive(Debug)]
+pub enum BindError {
+    /// Only JUMP_SLOT relocations are bound lazily
+    NotAJumpSlot(RelocationKind),
+    /// The relocation's symbol could not be read from the library's symbol table
+    UnknownSymbol(usize),
+    /// No loaded module defines the symbol
+    Unresolvable,
+    /// The relocation's slot at this address is misaligned or outside of the library's segments
+    InvalidSlot(usize),
+    Patch(PatchError),
+}
+
+impl Display for BindError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotAJumpSlot(kind) => write!(f, "`{kind:?}` relocations are never bound lazily"),
+            Self::UnknownSymbol(index) => write!(f, "Symbol `{index}` is not in the symbol table"),
+            Self::Unresolvable => write!(f, "No loaded module defines the symbol"),
+            Self::InvalidSlot(addr) => {
+                write!(f, "Slot `{addr:#x}` does not lie within the library")
+            }
+            Self::Patch(e) => write!(f, "Failed to write the slot: {e}"),
+        }
+    }
+}
+
+impl From<PatchError> for BindError {
+    fn from(value: PatchError) -> Self {
+        Self::Patch(value)
+    }
+}
+
+impl Error for BindError {}
+
+/// A `dlopen` reference on a library, released on drop
+struct Handle(*mut c_void);
+
+impl Handle {
+    /// Reference the already loaded library named `name`, the main program if it has no name
+    fn open(dyn_lib: &DynamicLibrary<'_>) -> Option<Self> {
+        let name = dyn_lib.library().name_cstr();
+        let path = match name.is_empty() {
+            true => core::ptr::null(),
+            false => name.as_ptr(),
+        };
+        let flags = libc::RTLD_NOLOAD | libc::RTLD_LAZY;
+        #[cfg(target_env = "gnu")]
+        let handle = match dyn_lib.library().namespace() {
+            Some(namespace) if !path.is_null() => unsafe {
+                libc::dlmopen(namespace.id(), path, flags)
+            },
+            _ => unsafe { libc::dlopen(path, flags) },
+        };
+        #[cfg(not(target_env = "gnu"))]
+        let handle = unsafe { libc::dlopen(path, flags) };
+        (!handle.is_null()).then_some(Self(handle))
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe { libc::dlclose(self.0) };
+    }
+}
+
+/// Look `name` up through `handle`, at `version` where the dynamic linker supports versioned lookups
+fn lookup(handle: *mut c_void, name: &CStr, version: Option<&CStr>) -> Option<usize> {
+    #[cfg(target_env = "gnu")]
+    let addr = match version {
+        Some(version) => unsafe { libc::dlvsym(handle, name.as_ptr(), version.as_ptr()) },
+        None => unsafe { libc::dlsym(handle, name.as_ptr()) },
+    };
+    #[cfg(not(target_env = "gnu"))]
+    let addr = {
+        let _ = version;
+        unsafe { libc::dlsym(handle, name.as_ptr()) }
+    };
+    (!addr.is_null()).then_some(addr as usize)
+}
+
+impl DynamicLibrary<'_> {
+    /// The value currently held by the slot `relocation` applies to.
+    /// None if the slot is misaligned or does not lie within one of the library's PT_LOAD segments.
+    pub fn slot_value(&self, relocation: &Relocation) -> Option<usize> {
+        let addr = self.relocation_addr(relocation);
+        let valid = addr.is_multiple_of(align_of::<usize>())
+            && self.library().contains_range(addr, size_of::<usize>());
+        valid.then(|| unsafe { (*(addr as *const AtomicUsize)).load(Ordering::Acquire) })
+    }
+
+    /// Is the JUMP_SLOT `relocation` still waiting on the dynamic linker's lazy binding.
+    /// Until its first call such a slot points back into the library's own PLT, at the stub calling the resolver,
+    /// so `patch` would hand back the resolver trampoline rather than the function.
+    ///
+    /// A slot is taken as unresolved if it points into one of the library's executable segments,
+    /// unless the library itself defines the symbol at that address. Slots outside of the library never are.
+    pub fn is_slot_unresolved(&self, relocation: &Relocation) -> bool {
+        if relocation.kind() != RelocationKind::JumpSlot {
+            return false;
+        }
+
+        let library = self.library();
+        let Some(value) = self.slot_value(relocation) else {
+            return false;
+        };
+        let in_code = library
+            .load_headers()
+            .any(|p_h| p_h.is_executable() && p_h.absolute_range(library.addr()).contains(&value));
+        let own_definition = self
+            .symbols()
+            .and_then(|symbols| symbols.entries().get(relocation.symbol_index()))
+            .filter(|symbol| symbol.is_defined())
+            .is_some_and(|symbol| library.addr().wrapping_add(symbol.st_value as usize) == value);
+        in_code && !own_definition
+    }
+
+    /// Bind the JUMP_SLOT `relocation` to its target now, as the dynamic linker would on the slot's first call,
+    /// so hooks installed afterwards get the real function back from `patch`.
+    /// Returns the address the slot holds, left untouched if it was already bound.
+    ///
+    /// The symbol is looked up at its required version, first in the global scope when the library shares our
+    /// namespace, then among the library and its dependencies.
+    /// An undefined weak symbol is bound to 0.
+    pub fn force_resolve(&self, relocation: &Relocation) -> Result<usize, BindError> {
+        let kind = relocation.kind();
+        if kind != RelocationKind::JumpSlot {
+            return Err(BindError::NotAJumpSlot(kind));
+        }
+        let current = self
+            .slot_value(relocation)
+            .ok_or(BindError::InvalidSlot(self.relocation_addr(relocation)))?;
+        if !self.is_slot_unresolved(relocation) {
+            return Ok(current);
+        }
+
+        let index = relocation.symbol_index();
+        let symbol = self
+            .symbols()
+            .and_then(|symbols| symbols.entries().get(index))
+            .ok_or(BindError::UnknownSymbol(index))?;
+        let name = self
+            .symbols()
+            .and_then(|symbols| symbols.resolve_cstr(index, self.string_table()))
+            .ok_or(BindError::UnknownSymbol(index))?;
+        let version = self
+            .versions()
+            .and_then(|versions| versions.resolve_version_cstr(index, self.string_table()));
+
+        let handle = Handle::open(self);
+        #[cfg(target_env = "gnu")]
+        let shares_global_scope = self.library().namespace()
+            == super::Namespace::containing(lookup as *const () as usize);
+        #[cfg(not(target_env = "gnu"))]
+        let shares_global_scope = true;
+        let global = shares_global_scope
+            .then(|| lookup(libc::RTLD_DEFAULT, name, version))
+            .flatten();
+        let target = global
+            .or_else(|| lookup(handle.as_ref()?.0, name, version))
+            .or((symbol.binding() == STB_WEAK).then_some(0))
+            .ok_or(BindError::Unresolvable)?;
+        self.write_slot(self.relocation_addr(relocation), target)?;
+        Ok(target)
+    }
+
+    /// Bind every JUMP_SLOT still waiting on lazy binding, see `force_resolve`.
+    /// Returns the number of slots bound, stopping at the first which can not be.
+    pub fn force_resolve_all(&self) -> Result<usize, BindError> {
+        let mut bound = 0;
+        for relocation in self.relocations() {
+            if self.is_slot_unresolved(&relocation) {
+                self.force_resolve(&relocation)?;
+                bound += 1;
+            }
+        }
+        Ok(bound)
+    }
+
+    /// Store `value` in the slot at `addr`, returning the value it held.
+    /// Slots writable in place are swapped without touching the page protection, others are made writable for the
+    /// write and then given back the protection of their segment.
+    pub(crate) fn write_slot(&self, addr: usize, value: usize) -> Result<usize, PatchError> {
+        write_protected(addr, value, self.library().page_protection(addr))
+    }
+}
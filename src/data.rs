@@ -0,0 +1,161 @@
+use super::{
+    for_each_module, DynamicLibrary, LoadedLibrary, PatchError, Relocation, RelocationKind,
+};
+use core::error::Error;
+use core::ffi::CStr;
+use core::fmt::Display;
+use core::ops::ControlFlow;
+
+/// Symbol type of data objects
+const STT_OBJECT: u8 = 1;
+/// Symbol type of common blocks, uninitialized data objects
+const STT_COMMON: u8 = 5;
+
+/// A relocation referencing a data object, such as `environ`, `stdout` or `__progname`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataRelocation<'a> {
+    pub relocation: Relocation,
+    pub name: &'a CStr,
+    /// Size of the object as recorded in the referencing symbol
+    pub size: usize,
+    /// Where the library accesses the object: the value of a GLOB_DAT entry, or the copy a COPY relocation
+    /// places in the executable
+    pub object: usize,
+}
+
+/// Errors redirecting a library's reference to a data object
+#[derive(Debug)]
+pub enum DataError {
+    /// Only GLOB_DAT entries hold the object's address, COPY relocations are accessed in place
+    NotAGotEntry(RelocationKind),
+    Patch(PatchError),
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotAGotEntry(kind) => {
+                write!(
+                    f,
+                    "`{kind:?}` relocations do not hold an address to redirect"
+                )
+            }
+            Self::Patch(e) => write!(f, "Failed to write the GOT entry: {e}"),
+        }
+    }
+}
+
+impl From<PatchError> for DataError {
+    fn from(value: PatchError) -> Self {
+        Self::Patch(value)
+    }
+}
+
+impl Error for DataError {}
+
+impl DynamicLibrary<'_> {
+    /// Iterate the GLOB_DAT and COPY relocations referencing data objects, with where the library accesses each object.
+    /// GLOB_DAT entries referencing functions, or whose slot lies outside of the library, are left out,
+    /// see `data_definition` for where an object is defined.
+    pub fn data_relocations(&self) -> impl Iterator<Item = DataRelocation<'_>> + '_ {
+        let string_table = self.string_table();
+        let symbols = self.symbols();
+        self.relocations().filter_map(move |relocation| {
+            let kind = relocation.kind();
+            if !matches!(kind, RelocationKind::GlobDat | RelocationKind::Copy) {
+                return None;
+            }
+            let index = relocation.symbol_index();
+            let symbol = symbols?.entries().get(index)?;
+            if kind == RelocationKind::GlobDat
+                && !matches!(symbol.symbol_type(), STT_OBJECT | STT_COMMON)
+            {
+                return None;
+            }
+            let name = symbols?.resolve_cstr(index, string_table)?;
+            let object = match kind {
+                RelocationKind::Copy => self.relocation_addr(&relocation),
+                _ => self.slot_value(&relocation)?,
+            };
+            Some(DataRelocation {
+                relocation,
+                name,
+                size: symbol.st_size as usize,
+                object,
+            })
+        })
+    }
+
+    /// Find the object `data` references as defined by the library providing it, which a COPY relocation copied
+    /// from at load time. Equal to `data.object` for GLOB_DAT entries unless the executable holds a copy.
+    ///
+    /// The modules sharing the library's namespace are searched in load order for a definition at the version the
+    /// library requires, or at the default version for unversioned references, skipping those holding a copy.
+    pub fn data_definition(&self, data: &DataRelocation<'_>) -> Option<usize> {
+        let required = self.versions().and_then(|versions| {
+            versions.resolve_version_cstr(data.relocation.symbol_index(), self.string_table())
+        });
+        let name = data.name.to_str().ok()?;
+        let visitor = |library: LoadedLibrary<'_>| {
+            let Ok(dyn_lib) = DynamicLibrary::initialize(library) else {
+                return ControlFlow::Continue(());
+            };
+            match dyn_lib.defined_object(data.name, required) {
+                Some(_)
+                    if dyn_lib
+                        .find_relocations(name)
+                        .any(|relocation| relocation.kind() == RelocationKind::Copy) =>
+                {
+                    ControlFlow::Continue(())
+                }
+                Some(addr) => ControlFlow::Break(addr),
+                None => ControlFlow::Continue(()),
+            }
+        };
+        #[cfg(target_env = "gnu")]
+        if let Some(namespace) = self.library().namespace() {
+            return super::for_each_module_in(namespace, visitor);
+        }
+        for_each_module(visitor)
+    }
+
+    /// Absolute address of the library's definition of the data object `name` at `version`, or at its default
+    /// version when no version is asked for
+    fn defined_object(&self, name: &CStr, version: Option<&CStr>) -> Option<usize> {
+        let symbols = self.symbols()?;
+        let versions = self.versions();
+        let index = (1..symbols.entries().len()).find(|&index| {
+            let symbol = &symbols.entries()[index];
+            let version_matches = match (version, versions) {
+                (Some(version), Some(versions)) => {
+                    versions.resolve_version_cstr(index, self.string_table()) == Some(version)
+                }
+                (None, Some(versions)) => !versions.is_hidden(index),
+                (_, None) => true,
+            };
+            symbol.is_defined()
+                && matches!(symbol.symbol_type(), STT_OBJECT | STT_COMMON)
+                && symbols.resolve_cstr(index, self.string_table()) == Some(name)
+                && version_matches
+        })?;
+        let value = symbols.entries()[index].st_value as usize;
+        Some(self.base_addr().wrapping_add(value))
+    }
+
+    /// Point the GLOB_DAT entry of `relocation` at `storage`, so the library reads and writes the data object there.
+    /// Returns the address the entry held, which restores the original object when redirected back to.
+    ///
+    /// Code which already copied the object's address elsewhere, and executables accessing a COPY relocated
+    /// object in place, are not affected.
+    pub fn redirect_data(
+        &self,
+        relocation: &Relocation,
+        storage: usize,
+    ) -> Result<usize, DataError> {
+        let kind = relocation.kind();
+        if kind != RelocationKind::GlobDat {
+            return Err(DataError::NotAGotEntry(kind));
+        }
+        Ok(self.write_slot(self.relocation_addr(relocation), storage)?)
+    }
+}
@@ -1,16 +1,37 @@
-use super::DynamicSectionType;
+use super::{DynamicSectionType, RelocationKind};
 use core::{error::Error, fmt::Display};
 
 pub type Word = libc::Elf64_Word;
 pub type Half = libc::Elf64_Half;
 pub type Addr = libc::Elf64_Addr;
 pub type ExtendedWord = libc::Elf64_Xword;
+pub type Offset = libc::Elf64_Off;
 
 // manual impl doesn't exist everywhere
 pub type ExtendedSignedWord = i64;
 
 pub type ProgramHeader = libc::Elf64_Phdr;
 
+/// The elf file header, found at the start of the first PT_LOAD segment
+#[repr(C)]
+#[derive(Debug)]
+pub struct FileHeader {
+    pub e_ident: [u8; 16],
+    pub e_type: self::Half,
+    pub e_machine: self::Half,
+    pub e_version: self::Word,
+    pub e_entry: self::Addr,
+    pub e_phoff: self::Offset,
+    pub e_shoff: self::Offset,
+    pub e_flags: self::Word,
+    pub e_ehsize: self::Half,
+    pub e_phentsize: self::Half,
+    pub e_phnum: self::Half,
+    pub e_shentsize: self::Half,
+    pub e_shnum: self::Half,
+    pub e_shstrndx: self::Half,
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct DynEntry {
@@ -30,6 +51,24 @@ pub struct DynSym {
     pub st_size: self::ExtendedWord,
 }
 
+/// Section index of undefined symbols
+pub const SHN_UNDEF: self::Half = 0;
+
+impl DynSym {
+    /// Does the object define the symbol, rather than import it
+    pub fn is_defined(&self) -> bool {
+        self.st_shndx != SHN_UNDEF
+    }
+    /// The symbol's type, such as STT_OBJECT or STT_FUNC
+    pub fn symbol_type(&self) -> u8 {
+        self.st_info & 0xf
+    }
+    /// The symbol's binding, such as STB_GLOBAL or STB_WEAK
+    pub fn binding(&self) -> u8 {
+        self.st_info >> 4
+    }
+}
+
 #[repr(C)]
 #[derive(Debug)]
 pub struct DynRel {
@@ -44,6 +83,9 @@ impl DynRel {
     pub fn symbol_type(&self) -> self::Word {
         (self.r_info & 0xffffffff) as self::Word
     }
+    pub fn kind(&self) -> RelocationKind {
+        RelocationKind::from(self.symbol_type())
+    }
 }
 
 #[repr(C)]
@@ -61,6 +103,9 @@ impl DynRela {
     pub fn symbol_type(&self) -> self::Word {
         (self.r_info & 0xffffffff) as self::Word
     }
+    pub fn kind(&self) -> RelocationKind {
+        RelocationKind::from(self.symbol_type())
+    }
 }
 
 /// An unknown Dynamic Section Type was observed
@@ -97,6 +142,32 @@ impl TryFrom<self::ExtendedWord> for DynamicSectionType {
 
             10 => DT_STRSZ,
             23 => DT_JMPREL,
+            14 => DT_SONAME,
+            21 => DT_DEBUG,
+            4 => DT_HASH,
+            0x6ffffef5 => DT_GNU_HASH,
+
+            30 => DT_FLAGS,
+            0x6ffffffb => DT_FLAGS_1,
+            24 => DT_BIND_NOW,
+            22 => DT_TEXTREL,
+            15 => DT_RPATH,
+            29 => DT_RUNPATH,
+
+            12 => DT_INIT,
+            13 => DT_FINI,
+            25 => DT_INIT_ARRAY,
+            26 => DT_FINI_ARRAY,
+            27 => DT_INIT_ARRAYSZ,
+            28 => DT_FINI_ARRAYSZ,
+            32 => DT_PREINIT_ARRAY,
+            33 => DT_PREINIT_ARRAYSZ,
+
+            0x6ffffff0 => DT_VERSYM,
+            0x6ffffffc => DT_VERDEF,
+            0x6ffffffd => DT_VERDEFNUM,
+            0x6ffffffe => DT_VERNEED,
+            0x6fffffff => DT_VERNEEDNUM,
 
             tag => return Err(DynTypeError(tag)),
         })
@@ -0,0 +1,114 @@
+use super::{elf, DynamicLibrary, PatchError};
+use core::error::Error;
+use core::fmt::Display;
+
+/// Symbol type of indirect functions, whose value is the resolver
+const STT_GNU_IFUNC: u8 = 10;
+
+/// Errors hooking a library's exported symbol
+#[derive(Debug)]
+pub enum ExportError {
+    /// The library does not define the symbol
+    NotDefined,
+    /// The symbol is an ifunc, the dynamic linker would call the hook as its resolver
+    IndirectFunction,
+    Patch(PatchError),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotDefined => write!(f, "The library does not define the symbol"),
+            Self::IndirectFunction => write!(f, "Indirect functions can not be hooked by value"),
+            Self::Patch(e) => write!(f, "Failed to write the symbol: {e}"),
+        }
+    }
+}
+
+impl From<PatchError> for ExportError {
+    fn from(value: PatchError) -> Self {
+        Self::Patch(value)
+    }
+}
+
+impl Error for ExportError {}
+
+/// An export hook installed by `DynamicLibrary::hook_export`, undone with `DynamicLibrary::unhook_export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[must_use]
+pub struct ExportHook {
+    symbol_index: usize,
+    st_value: usize,
+    original: usize,
+}
+
+impl ExportHook {
+    /// Index of the hooked symbol in the dynamic symbol table
+    pub fn symbol_index(&self) -> usize {
+        self.symbol_index
+    }
+
+    /// Absolute address of the hooked definition, for the hook to call through
+    pub fn original(&self) -> usize {
+        self.original
+    }
+}
+
+impl DynamicLibrary<'_> {
+    /// Overwrite the `st_value` of the dynamic symbol at `index`.
+    /// The symbol table is usually read only, its page is made writable for the write and given back the
+    /// protection of the segment holding it, without write access within PT_GNU_RELRO.
+    fn write_symbol_value(&self, index: usize, value: usize) -> Result<(), ExportError> {
+        let symbol = self
+            .symbols()
+            .and_then(|symbols| symbols.entries().get(index))
+            .ok_or(ExportError::NotDefined)?;
+        let addr = &symbol.st_value as *const elf::Addr as usize;
+        self.write_slot(addr, value)?;
+        Ok(())
+    }
+
+    /// Hook the library's export `symbol_name` by rewriting its symbol's `st_value`, so every lookup the dynamic
+    /// linker performs afterwards binds to `hook`: lazily bound PLT slots, libraries loaded later and `dlsym`.
+    /// The default version is hooked when the library defines several.
+    ///
+    /// Unlike `patch` this is process wide and does not reach slots bound before the hook, hook those separately.
+    /// `st_value` is relative to the load bias, which is 0 for ET_EXEC executables.
+    pub fn hook_export(&self, symbol_name: &str, hook: usize) -> Result<ExportHook, ExportError> {
+        let symbols = self.symbols().ok_or(ExportError::NotDefined)?;
+        let is_hidden = |index| {
+            self.versions()
+                .is_some_and(|versions| versions.is_hidden(index))
+        };
+        let mut definitions = (1..symbols.entries().len()).filter(|&index| {
+            symbols.entries()[index].is_defined()
+                && symbols
+                    .resolve_cstr(index, self.string_table())
+                    .is_some_and(|name| name.to_bytes() == symbol_name.as_bytes())
+        });
+        let index = definitions
+            .clone()
+            .find(|&index| !is_hidden(index))
+            .or_else(|| definitions.next())
+            .ok_or(ExportError::NotDefined)?;
+
+        let symbol = &symbols.entries()[index];
+        if symbol.symbol_type() == STT_GNU_IFUNC {
+            return Err(ExportError::IndirectFunction);
+        }
+        let st_value = symbol.st_value as usize;
+        let base = self.base_addr();
+        self.write_symbol_value(index, hook.wrapping_sub(base))?;
+        Ok(ExportHook {
+            symbol_index: index,
+            st_value,
+            original: base.wrapping_add(st_value),
+        })
+    }
+
+    /// Restore the `st_value` an export hook replaced.
+    /// Slots bound to the hook while it was installed keep calling it.
+    pub fn unhook_export(&self, hook: ExportHook) -> Result<(), ExportError> {
+        self.write_symbol_value(hook.symbol_index, hook.st_value)
+    }
+}
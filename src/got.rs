@@ -0,0 +1,120 @@
+use super::{
+    arch, checked_slice, for_each_module, link_map::link_map_dynamic, rebase, DynamicLibrary,
+    DynamicSectionType, ModuleKind,
+};
+use core::error::Error;
+use core::fmt::Display;
+use core::ops::ControlFlow;
+
+/// The GOT entries DT_PLTGOT reserves for the dynamic linker, as found in the loaded module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReservedGot {
+    /// Absolute address of the GOT, where DT_PLTGOT points
+    pub addr: usize,
+    /// GOT[0], the link time address of the module's `_DYNAMIC`, left unrelocated by the dynamic linker
+    pub dynamic: usize,
+    /// GOT[1], the module's `struct link_map`.
+    /// Only filled in by glibc for modules bound lazily, 0 otherwise and under musl or bionic.
+    pub link_map: usize,
+    /// GOT[2], the dynamic linker's lazy binding entry point such as `_dl_runtime_resolve`.
+    /// Only filled in alongside `link_map`.
+    pub resolver: usize,
+}
+
+impl ReservedGot {
+    /// Has the dynamic linker set the module up for lazy binding
+    pub fn is_lazy_binding_set_up(&self) -> bool {
+        self.link_map != 0 && self.resolver != 0
+    }
+}
+
+/// Inconsistencies found verifying a module's reserved GOT entries
+#[derive(Debug)]
+pub enum GotError {
+    /// The module has no DT_PLTGOT, it lies outside of the module, or the architecture's layout is not known
+    Missing,
+    /// GOT[0] does not hold the address of the module's dynamic section
+    DynamicMismatch(usize),
+    /// GOT[1] is not a link_map entry of the dynamic linker, or the entry describes another module
+    LinkMapMismatch(usize),
+    /// GOT[2] points outside of the dynamic linker, its lazy binding entry point was replaced
+    HijackedResolver(usize),
+}
+
+impl Display for GotError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Missing => write!(f, "No reserved GOT entries available"),
+            Self::DynamicMismatch(value) => write!(
+                f,
+                "GOT[0] `{value:#X}` is not the address of the dynamic section"
+            ),
+            Self::LinkMapMismatch(value) => {
+                write!(f, "GOT[1] `{value:#X}` is not the module's link_map")
+            }
+            Self::HijackedResolver(value) => write!(
+                f,
+                "GOT[2] `{value:#X}` points outside of the dynamic linker"
+            ),
+        }
+    }
+}
+
+impl Error for GotError {}
+
+/// Does `addr` lie within the dynamic linker's segments, true if the dynamic linker can not be found
+fn in_dynamic_linker(addr: usize) -> bool {
+    for_each_module(|library| match library.kind() {
+        ModuleKind::DynamicLinker => ControlFlow::Break(library.contains_addr(addr)),
+        _ => ControlFlow::Continue(()),
+    })
+    .unwrap_or(true)
+}
+
+impl DynamicLibrary<'_> {
+    /// Absolute address of the GOT DT_PLTGOT points at, if the library has one
+    pub fn got_addr(&self) -> Option<usize> {
+        let pltgot = self
+            .dyn_section()
+            .find_section(DynamicSectionType::DT_PLTGOT)?;
+        Some(rebase(self.library(), pltgot.d_val_ptr as usize))
+    }
+
+    /// Read the GOT entries reserved for the dynamic linker, decoded for the target architecture
+    pub fn reserved_got(&self) -> Option<ReservedGot> {
+        let [dynamic, link_map, resolver] = arch::reserved_got_indices()?;
+        let addr = self.got_addr()?;
+        let entries = checked_slice::<usize>(self.library(), addr, 3)?;
+        Some(ReservedGot {
+            addr,
+            dynamic: entries[dynamic],
+            link_map: entries[link_map],
+            resolver: entries[resolver],
+        })
+    }
+
+    /// Read the reserved GOT entries and check them against what the dynamic linker knows of the library:
+    /// GOT[0] has to locate the dynamic section, and once lazy binding is set up GOT[1] has to be the library's
+    /// link_map and GOT[2] has to point into the dynamic linker.
+    pub fn verify_got(&self) -> Result<ReservedGot, GotError> {
+        let got = self.reserved_got().ok_or(GotError::Missing)?;
+
+        let library = self.library();
+        let dynamic_addr = self.dyn_section().entries().as_ptr() as usize;
+        if got.dynamic != 0
+            && got.dynamic != dynamic_addr
+            && library.addr().wrapping_add(got.dynamic) != dynamic_addr
+        {
+            return Err(GotError::DynamicMismatch(got.dynamic));
+        }
+
+        if got.link_map != 0 && link_map_dynamic(got.link_map) != Some(dynamic_addr) {
+            return Err(GotError::LinkMapMismatch(got.link_map));
+        }
+
+        if got.resolver != 0 && !in_dynamic_linker(got.resolver) {
+            return Err(GotError::HijackedResolver(got.resolver));
+        }
+        Ok(got)
+    }
+}
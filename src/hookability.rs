@@ -0,0 +1,106 @@
+use super::{DynamicLibrary, Relocation, RelocationKind};
+use alloc::vec::Vec;
+
+/// Why a symbol can or can not be hooked in a library
+#[derive(Debug)]
+pub enum HookReason {
+    /// Called through a PLT slot, which `patch` can redirect
+    PltSlot,
+    /// Only referenced through GLOB_DAT GOT entries, as `-fno-plt` call sites and address taken functions are.
+    /// The slots can be redirected, pointers already read from them are not affected.
+    GotOnly,
+    /// The symbol is an ifunc the library resolves for itself through IRELATIVE slots, which have no symbol
+    IfuncOnly,
+    /// The library defines the symbol and binds its own calls directly, as protected visibility,
+    /// `-Bsymbolic` or link time optimization do, so no slot exists
+    DefinedLocally,
+    /// Only referenced by relocations `patch` can not redirect, such as absolute addresses or COPY relocations
+    UnsupportedRelocations,
+    /// The library neither references nor exports the symbol
+    NotImported,
+}
+
+/// The outcome of `DynamicLibrary::explain_hook`
+#[derive(Debug)]
+pub struct HookDiagnosis {
+    pub reason: HookReason,
+    /// Every relocation found for the symbol, IRELATIVE ones included
+    pub relocations: Vec<Relocation>,
+    /// Some hookable slot lies on a page made read only after relocation, typically with full RELRO.
+    /// `patch` has to make the page writable with `mprotect` first, which hardened systems may refuse.
+    pub read_only: bool,
+}
+
+impl HookDiagnosis {
+    /// Can the symbol be hooked by patching the reported relocations
+    pub fn is_hookable(&self) -> bool {
+        matches!(self.reason, HookReason::PltSlot | HookReason::GotOnly)
+    }
+}
+
+impl DynamicLibrary<'_> {
+    /// Does the library export a definition of `symbol_name`
+    fn defines(&self, symbol_name: &str) -> bool {
+        let Some(symbols) = self.symbols() else {
+            return false;
+        };
+        (1..symbols.entries().len()).any(|index| {
+            symbols.entries()[index].is_defined()
+                && symbols
+                    .resolve_cstr(index, self.string_table())
+                    .is_some_and(|name| name.to_bytes() == symbol_name.as_bytes())
+        })
+    }
+
+    /// Explain why `symbol_name` can or can not be hooked in the library, along with the relocations found for it.
+    /// Where `try_find_function` only answers `None`, this tells a symbol the library does not import apart from
+    /// one it calls internally, only takes the address of or dispatches as an ifunc, and whether its slots are read only.
+    ///
+    /// Nothing is written, read only slots are told apart from the PT_LOAD and PT_GNU_RELRO headers.
+    pub fn explain_hook(&self, symbol_name: &str) -> HookDiagnosis {
+        let mut relocations = self.find_relocations(symbol_name).collect::<Vec<_>>();
+        let hookable = relocations
+            .iter()
+            .filter(|relocation| relocation.kind().is_hookable());
+        let read_only = hookable.clone().any(|relocation| {
+            let prot = self
+                .library()
+                .page_protection(self.relocation_addr(relocation));
+            prot & libc::PROT_WRITE == 0
+        });
+        let has_jump_slot = hookable
+            .clone()
+            .any(|relocation| relocation.kind() == RelocationKind::JumpSlot);
+        let has_hookable = hookable.count() > 0;
+        let has_others = !relocations.is_empty();
+
+        let ifuncs = self
+            .ifunc_relocations()
+            .filter(|ifunc| {
+                ifunc
+                    .resolver_symbol
+                    .is_some_and(|name| name.to_bytes() == symbol_name.as_bytes())
+            })
+            .map(|ifunc| ifunc.relocation);
+        relocations.extend(ifuncs);
+
+        let reason = if has_jump_slot {
+            HookReason::PltSlot
+        } else if has_hookable {
+            HookReason::GotOnly
+        } else if has_others {
+            HookReason::UnsupportedRelocations
+        } else if !relocations.is_empty() {
+            HookReason::IfuncOnly
+        } else if self.defines(symbol_name) {
+            HookReason::DefinedLocally
+        } else {
+            HookReason::NotImported
+        };
+        HookDiagnosis {
+            reason,
+            relocations,
+            read_only,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use super::{DynamicLibrary, Relocation, RelocationKind};
+use core::ffi::CStr;
+
+/// An IRELATIVE relocation, a slot the dynamic linker fills with the implementation an ifunc resolver picked.
+/// These have no symbol, the function is only known through its resolver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IfuncRelocation<'a> {
+    pub relocation: Relocation,
+    /// Absolute address of the resolver.
+    /// Only known for relocations with an explicit addend, addendless tables keep it in the slot, which has since
+    /// been overwritten with the implementation.
+    pub resolver: Option<usize>,
+    /// The dynamic symbol the resolver belongs to, such as `memcpy` for glibc's own string functions
+    pub resolver_symbol: Option<&'a CStr>,
+    /// The implementation currently stored in the slot.
+    /// May be 0 for resolvers only run for their side effects, as glibc does to initialize its cache info.
+    pub implementation: usize,
+}
+
+impl DynamicLibrary<'_> {
+    /// Iterate the library's IRELATIVE relocations with their resolver and the implementation their slot holds.
+    /// The slots can be redirected with `patch` like any other, to hook ifunc dispatched functions.
+    /// Relocations whose slot lies outside of the library are skipped.
+    pub fn ifunc_relocations(&self) -> impl Iterator<Item = IfuncRelocation<'_>> + '_ {
+        self.relocations()
+            .filter(|relocation| relocation.kind() == RelocationKind::IRelative)
+            .filter_map(|relocation| {
+                let resolver = relocation
+                    .addend()
+                    .map(|addend| self.library().addr().wrapping_add_signed(addend));
+                Some(IfuncRelocation {
+                    relocation,
+                    resolver,
+                    resolver_symbol: resolver.and_then(|resolver| self.symbol_at(resolver)),
+                    implementation: self.slot_value(&relocation)?,
+                })
+            })
+    }
+}
@@ -0,0 +1,99 @@
+use super::{DynamicLibrary, SelectedRelocation, SymbolSelector};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ffi::CStr;
+
+/// A per library index of every symbol referencing relocation, sorted by symbol name and version.
+/// Built once with a single pass over the relocation tables, lookups are then a binary search
+/// instead of the linear scan and name resolution `DynamicLibrary::find_relocations` performs per call.
+pub struct SymbolIndex<'a> {
+    entries: Vec<SelectedRelocation<'a>>,
+}
+
+/// Order entries by name, then by version, unversioned first
+fn entry_cmp(name: &[u8], version: Option<&[u8]>, entry: &SelectedRelocation<'_>) -> Ordering {
+    entry
+        .name
+        .to_bytes()
+        .cmp(name)
+        .then_with(|| entry.version.map(CStr::to_bytes).cmp(&version))
+}
+
+impl<'a> SymbolIndex<'a> {
+    /// Index every relocation of `library` referencing a named symbol
+    pub fn build(library: &'a DynamicLibrary<'_>) -> Self {
+        let string_table = library.string_table();
+        let symbols = library.symbols();
+        let versions = library.versions();
+        let mut entries = library
+            .relocations()
+            .filter(|relocation| relocation.symbol_index() != 0)
+            .filter_map(|relocation| {
+                let name = symbols?.resolve_cstr(relocation.symbol_index(), string_table)?;
+                let version = versions
+                    .and_then(|v| v.resolve_version_cstr(relocation.symbol_index(), string_table));
+                Some(SelectedRelocation {
+                    relocation,
+                    name,
+                    version,
+                })
+            })
+            .filter(|entry| !entry.name.is_empty())
+            .collect::<Vec<_>>();
+
+        // stable so relocations of the same symbol keep their table order
+        entries.sort_by(|a, b| entry_cmp(b.name.to_bytes(), b.version.map(CStr::to_bytes), a));
+        Self { entries }
+    }
+
+    /// Every relocation referencing the symbol named `name`, of any version
+    pub fn find(&self, name: &str) -> &[SelectedRelocation<'a>] {
+        let start = self
+            .entries
+            .partition_point(|entry| entry.name.to_bytes() < name.as_bytes());
+        let len = self.entries[start..]
+            .iter()
+            .take_while(|entry| entry.name.to_bytes() == name.as_bytes())
+            .count();
+        &self.entries[start..start + len]
+    }
+
+    /// Every relocation referencing the symbol named `name` with exactly the version `version`.
+    /// `None` selects the unversioned symbol.
+    pub fn find_versioned(&self, name: &str, version: Option<&str>) -> &[SelectedRelocation<'a>] {
+        let version = version.map(str::as_bytes);
+        let start = self
+            .entries
+            .partition_point(|entry| entry_cmp(name.as_bytes(), version, entry) == Ordering::Less);
+        let len = self.entries[start..]
+            .iter()
+            .take_while(|entry| entry_cmp(name.as_bytes(), version, entry) == Ordering::Equal)
+            .count();
+        &self.entries[start..start + len]
+    }
+
+    /// Every relocation referencing `name` whose slot can be redirected with `patch`
+    pub fn find_hookable<'b>(
+        &'b self,
+        name: &str,
+    ) -> impl Iterator<Item = &'b SelectedRelocation<'a>> + 'b {
+        self.find(name)
+            .iter()
+            .filter(|entry| entry.relocation.kind().is_hookable())
+    }
+
+    /// Every indexed relocation picked by `selector`
+    pub fn select<'b>(
+        &'b self,
+        selector: &'b SymbolSelector<'_>,
+    ) -> impl Iterator<Item = &'b SelectedRelocation<'a>> + 'b {
+        self.entries.iter().filter(|entry| {
+            selector.matches(entry.name.to_bytes(), entry.version.map(CStr::to_bytes))
+        })
+    }
+
+    /// Every indexed relocation, sorted by symbol name and version
+    pub fn entries(&self) -> &[SelectedRelocation<'a>] {
+        &self.entries
+    }
+}
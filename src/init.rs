@@ -0,0 +1,175 @@
+use super::{checked_slice, elf, rebase, DynamicLibrary, DynamicSectionType, PatchError};
+use core::error::Error;
+use core::ffi::CStr;
+use core::fmt::Display;
+
+/// Where a constructor or destructor is registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitKind {
+    /// DT_PREINIT_ARRAY, run before any other constructor, only honoured for the executable
+    PreinitArray,
+    /// DT_INIT, the legacy `_init` function
+    Init,
+    /// DT_INIT_ARRAY, `__attribute__((constructor))` functions and C++ static initializers
+    InitArray,
+    /// DT_FINI_ARRAY, `__attribute__((destructor))` functions, run in reverse order
+    FiniArray,
+    /// DT_FINI, the legacy `_fini` function, run after the fini array
+    Fini,
+}
+
+impl InitKind {
+    /// Does the function run at unload or exit rather than at load
+    pub fn is_destructor(&self) -> bool {
+        matches!(self, Self::FiniArray | Self::Fini)
+    }
+
+    /// The array entry's tag and the tag holding its size in bytes, None for DT_INIT and DT_FINI
+    fn array_tags(&self) -> Option<(DynamicSectionType, DynamicSectionType)> {
+        match self {
+            Self::PreinitArray => Some((
+                DynamicSectionType::DT_PREINIT_ARRAY,
+                DynamicSectionType::DT_PREINIT_ARRAYSZ,
+            )),
+            Self::InitArray => Some((
+                DynamicSectionType::DT_INIT_ARRAY,
+                DynamicSectionType::DT_INIT_ARRAYSZ,
+            )),
+            Self::FiniArray => Some((
+                DynamicSectionType::DT_FINI_ARRAY,
+                DynamicSectionType::DT_FINI_ARRAYSZ,
+            )),
+            Self::Init | Self::Fini => None,
+        }
+    }
+}
+
+/// Errors replacing a library's constructor or destructor
+#[derive(Debug)]
+pub enum InitError {
+    /// The function is not one of the library's, its slot could lie anywhere
+    NotInLibrary,
+    Patch(PatchError),
+}
+
+impl Display for InitError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotInLibrary => write!(f, "The function is not registered by the library"),
+            Self::Patch(e) => write!(f, "Failed to write the function's slot: {e}"),
+        }
+    }
+}
+
+impl From<PatchError> for InitError {
+    fn from(value: PatchError) -> Self {
+        Self::Patch(value)
+    }
+}
+
+impl Error for InitError {}
+
+/// A constructor or destructor of a library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InitFunction<'a> {
+    pub kind: InitKind,
+    /// Position within its array, 0 for DT_INIT and DT_FINI
+    pub index: usize,
+    /// Address of the array entry, or of the dynamic entry's value for DT_INIT and DT_FINI
+    pub slot: usize,
+    /// Absolute address of the function
+    pub addr: usize,
+    /// The dynamic symbol defined at `addr`.
+    /// Constructors are often local functions missing from the dynamic symbol table.
+    pub symbol: Option<&'a CStr>,
+}
+
+impl DynamicLibrary<'_> {
+    /// The entries of the array registered under `kind`
+    fn init_array(&self, kind: InitKind) -> &[usize] {
+        let Some((array_tag, size_tag)) = kind.array_tags() else {
+            return &[];
+        };
+        let (Some(array), Some(size)) = (
+            self.dyn_section().find_section(array_tag),
+            self.dyn_section().find_section(size_tag),
+        ) else {
+            return &[];
+        };
+        let addr = rebase(self.library(), array.d_val_ptr as usize);
+        let count = size.d_val_ptr as usize / size_of::<usize>();
+        checked_slice::<usize>(self.library(), addr, count).unwrap_or_default()
+    }
+
+    /// The dynamic entry of DT_INIT or DT_FINI
+    fn init_entry(&self, kind: InitKind) -> Option<&elf::DynEntry> {
+        let tag = match kind {
+            InitKind::Init => DynamicSectionType::DT_INIT,
+            InitKind::Fini => DynamicSectionType::DT_FINI,
+            _ => return None,
+        };
+        self.dyn_section().find_section(tag)
+    }
+
+    /// Iterate the library's constructors and destructors in the order they are registered: the preinit array,
+    /// DT_INIT, the init array, then the fini array and DT_FINI.
+    /// The legacy 0 and -1 array markers are reported as they are.
+    pub fn init_functions(&self) -> impl Iterator<Item = InitFunction<'_>> + '_ {
+        let library = self.library();
+        let function = move |kind, index, slot, addr| InitFunction {
+            kind,
+            index,
+            slot,
+            addr,
+            symbol: self.symbol_at(addr),
+        };
+        let array = move |kind| {
+            self.init_array(kind)
+                .iter()
+                .enumerate()
+                .map(move |(index, addr)| {
+                    function(kind, index, addr as *const usize as usize, *addr)
+                })
+        };
+        let entry = move |kind| {
+            self.init_entry(kind).map(|entry| {
+                let slot = &entry.d_val_ptr as *const _ as usize;
+                function(kind, 0, slot, rebase(library, entry.d_val_ptr as usize))
+            })
+        };
+        array(InitKind::PreinitArray)
+            .chain(entry(InitKind::Init))
+            .chain(array(InitKind::InitArray))
+            .chain(array(InitKind::FiniArray))
+            .chain(entry(InitKind::Fini))
+    }
+
+    /// Replace the constructor or destructor `function` with `replacement`, an absolute address, returning the
+    /// function it replaced.
+    /// Replacing a destructor before `dlclose` keeps a misbehaving one from running, constructors have already run
+    /// by the time the library can be inspected.
+    ///
+    /// Fails with `InitError::NotInLibrary` unless `function` was listed by this library's `init_functions`.
+    pub fn replace_init_function(
+        &self,
+        function: &InitFunction<'_>,
+        replacement: usize,
+    ) -> Result<usize, InitError> {
+        let registered = self
+            .init_functions()
+            .any(|listed| listed.kind == function.kind && listed.slot == function.slot);
+        if !registered {
+            return Err(InitError::NotInLibrary);
+        }
+        // DT_INIT and DT_FINI hold a virtual address, which the dynamic linker rebases when it calls them
+        let value = match function.kind.array_tags() {
+            Some(_) => replacement,
+            None => replacement.wrapping_sub(self.base_addr()),
+        };
+        let previous = self.write_slot(function.slot, value)?;
+        Ok(match function.kind.array_tags() {
+            Some(_) => previous,
+            None => self.base_addr().wrapping_add(previous),
+        })
+    }
+}
@@ -1,12 +1,17 @@
 #![no_std]
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "alloc")]
 use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::error::Error;
-use core::ffi::c_void;
+use core::ffi::{c_void, CStr};
 use core::fmt::Display;
 use core::mem::size_of;
+use core::ops::{ControlFlow, Range};
 use core::result::Result;
+use core::sync::atomic::{AtomicUsize, Ordering};
 #[cfg(target_pointer_width = "64")]
 pub mod elf64;
 #[cfg(target_pointer_width = "64")]
@@ -15,6 +20,51 @@ use elf64 as elf;
 pub mod elf32;
 #[cfg(target_pointer_width = "32")]
 use elf32 as elf;
+mod arch;
+mod binding;
+mod data;
+mod export;
+mod got;
+#[cfg(feature = "alloc")]
+mod hookability;
+mod ifunc;
+#[cfg(feature = "alloc")]
+mod index;
+mod init;
+mod link_map;
+#[cfg(feature = "alloc")]
+mod module;
+mod namespace;
+mod note;
+mod plt;
+#[cfg(feature = "alloc")]
+mod registry;
+mod security;
+mod selector;
+pub mod version;
+pub use binding::BindError;
+pub use data::{DataError, DataRelocation};
+pub use export::{ExportError, ExportHook};
+pub use got::{GotError, ReservedGot};
+#[cfg(feature = "alloc")]
+pub use hookability::{HookDiagnosis, HookReason};
+pub use ifunc::IfuncRelocation;
+#[cfg(feature = "alloc")]
+pub use index::SymbolIndex;
+pub use init::{InitError, InitFunction, InitKind};
+pub use link_map::{for_each_link_map_entry, for_each_module_in, LinkMapEntry};
+#[cfg(feature = "alloc")]
+pub use module::{
+    find_module_by_name, find_module_by_soname, main_executable, module_containing, ModuleSnapshot,
+};
+pub use namespace::Namespace;
+pub use note::{AbiTag, GnuProperties, GnuProperty, Note, NoteKind};
+pub use plt::PltStub;
+#[cfg(feature = "alloc")]
+pub use registry::{ModuleChange, ModuleEvent, ModuleRegistry};
+pub use security::{ControlFlowProtection, Relro, SecurityReport};
+pub use selector::{Glob, SymbolSelector};
+use version::SymbolVersions;
 
 /// Errors related to dynamic libraries
 #[derive(Debug)]
@@ -23,6 +73,10 @@ pub enum DynamicError {
     DependentSection(DynamicSectionType, DynamicSectionType),
     RequiredSection(DynamicSectionType),
     ProgramHeader,
+    Unpinned,
+    EntrySize(DynamicSectionType, usize),
+    OutOfBounds(DynamicSectionType),
+    DynamicSectionBounds,
 }
 
 impl Display for DynamicError {
@@ -38,6 +92,18 @@ impl Display for DynamicError {
                 "Failed to parse, required section missing `{required:#?}`"
             ),
             Self::ProgramHeader => write!(f, "No dynamic program header available"),
+            Self::Unpinned => write!(f, "Module is not pinned and may be unloaded"),
+            Self::EntrySize(tag, size) => {
+                write!(f, "Unexpected entry size `{size}` given by `{tag:#?}`")
+            }
+            Self::OutOfBounds(tag) => write!(
+                f,
+                "`{tag:#?}` points outside of the library's loaded segments"
+            ),
+            Self::DynamicSectionBounds => write!(
+                f,
+                "The dynamic section lies outside of the library's loaded segments"
+            ),
         }
     }
 }
@@ -50,6 +116,21 @@ impl From<elf::DynTypeError> for DynamicError {
 
 impl Error for DynamicError {}
 
+/// A table `DynamicLibrary::initialize_lenient` could not parse, and left unavailable
+#[derive(Debug)]
+pub struct ParseWarning {
+    /// The tag locating the table, such as DT_RELA for the addend relocations
+    pub table: DynamicSectionType,
+    /// Why the table could not be parsed
+    pub error: DynamicError,
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "`{:#?}` unavailable: {}", self.table, self.error)
+    }
+}
+
 /// Section type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[allow(non_camel_case_types)]
@@ -73,9 +154,36 @@ pub enum DynamicSectionType {
 
     DT_STRSZ,
     DT_JMPREL,
+    DT_SONAME,
+    DT_DEBUG,
+    DT_HASH,
+    DT_GNU_HASH,
+
+    DT_FLAGS,
+    DT_FLAGS_1,
+    DT_BIND_NOW,
+    DT_TEXTREL,
+    DT_RPATH,
+    DT_RUNPATH,
+
+    DT_INIT,
+    DT_FINI,
+    DT_INIT_ARRAY,
+    DT_FINI_ARRAY,
+    DT_INIT_ARRAYSZ,
+    DT_FINI_ARRAYSZ,
+    DT_PREINIT_ARRAY,
+    DT_PREINIT_ARRAYSZ,
+
+    DT_VERSYM,
+    DT_VERDEF,
+    DT_VERDEFNUM,
+    DT_VERNEED,
+    DT_VERNEEDNUM,
 }
 
 /// Container of Dynamic Relocations
+#[derive(Clone, Copy)]
 pub struct DynamicRelocations<'a> {
     inner: &'a [elf::DynRel],
 }
@@ -93,6 +201,7 @@ impl DynamicRelocations<'_> {
 }
 
 /// Container of Dynamic Addend Relocations
+#[derive(Clone, Copy)]
 pub struct DynamicAddendRelocations<'a> {
     inner: &'a [elf::DynRela],
 }
@@ -110,17 +219,24 @@ impl DynamicAddendRelocations<'_> {
 }
 
 /// Container of Dynamic Symbols
+#[derive(Clone, Copy)]
 pub struct DynamicSymbols<'a> {
-    inner: &'a elf::DynSym,
+    inner: &'a [elf::DynSym],
 }
 
 impl DynamicSymbols<'_> {
     /// gets the dynamic symbol at this index
     fn get(&self, index: usize) -> Option<&elf::DynSym> {
-        unsafe { (self.inner as *const elf::DynSym).add(index).as_ref() }
+        self.inner.get(index)
+    }
+
+    /// Dynamic symbols internal slice
+    pub fn entries(&self) -> &[elf::DynSym] {
+        self.inner
     }
 
     /// resolves the name of the dynamic symbol at `index`
+    #[cfg(feature = "alloc")]
     pub fn resolve_name<'b>(
         &'b self,
         index: usize,
@@ -129,14 +245,25 @@ impl DynamicSymbols<'_> {
         let entry = self.get(index)?;
         string_table.read_at(entry.st_name as usize)
     }
+
+    /// resolves the name of the dynamic symbol at `index` without allocating
+    pub fn resolve_cstr<'b>(
+        &self,
+        index: usize,
+        string_table: &StringTable<'b>,
+    ) -> Option<&'b CStr> {
+        let entry = self.get(index)?;
+        string_table.read_cstr_at(entry.st_name as usize)
+    }
 }
 
 /// Container of Dynamic Entries
+#[derive(Clone, Copy)]
 pub struct DynamicSection<'a> {
-    inner: &'a elf::DynEntry,
+    inner: &'a [elf::DynEntry],
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 /// A view of the Library's String table
 /// The inner `raw` reference refers to a continguous array of zero terminated strings.
 /// The string table view is needed to arbitrarily access into the data and pull out the null terminated strings.
@@ -146,13 +273,26 @@ pub struct StringTable<'a> {
 
 impl<'a> StringTable<'a> {
     /// Extract string from table starting at carrot position
+    /// Allocates if the string is not valid UTF-8, see `read_cstr_at` for an allocation free alternative.
+    #[cfg(feature = "alloc")]
     pub fn read_at(&'a self, carrot: usize) -> Option<Cow<'a, str>> {
-        match carrot >= self.raw.len() {
-            true => None,
-            false => unsafe {
-                Some(core::ffi::CStr::from_ptr(&self.raw[carrot]).to_string_lossy())
-            },
-        }
+        self.read_cstr_at(carrot).map(CStr::to_string_lossy)
+    }
+
+    /// Extract the null terminated string starting at carrot position, without allocating.
+    /// Strings which are not terminated within the table are rejected.
+    pub fn read_cstr_at(&self, carrot: usize) -> Option<&'a CStr> {
+        CStr::from_bytes_until_nul(self.bytes().get(carrot..)?).ok()
+    }
+
+    /// Extract the bytes of the string starting at carrot position, excluding the null terminator
+    pub fn read_bytes_at(&self, carrot: usize) -> Option<&'a [u8]> {
+        self.read_cstr_at(carrot).map(CStr::to_bytes)
+    }
+
+    /// The table's raw contents as bytes
+    fn bytes(&self) -> &'a [u8] {
+        unsafe { core::slice::from_raw_parts(self.raw.as_ptr().cast::<u8>(), self.raw.len()) }
     }
 
     /// total size of the string table in memory.
@@ -163,10 +303,10 @@ impl<'a> StringTable<'a> {
 }
 
 impl DynamicSection<'_> {
-    /// Iterate dynamic section's DynEntry link list attempting to find section with target section type
+    /// Iterate dynamic section's DynEntry list attempting to find section with target section type.
+    /// The walk stops at DT_NULL, or at the end of the PT_DYNAMIC segment for sections missing one.
     fn find_section(&self, tag: DynamicSectionType) -> Option<&elf::DynEntry> {
-        let mut current = Some(self.inner);
-        while let Some(inner) = current {
+        for inner in self.inner {
             match DynamicSectionType::try_from(inner.d_tag) {
                 Ok(DynamicSectionType::DT_NULL) => return None,
                 Ok(this_tag) if this_tag == tag => return Some(inner),
@@ -177,31 +317,285 @@ impl DynamicSection<'_> {
                     // continue for now...;
                 }
             }
-
-            current = unsafe { (inner as *const elf::DynEntry).offset(1).as_ref() };
         }
 
         None
     }
+
+    /// Dynamic section's entries, up to the end of the PT_DYNAMIC segment
+    pub fn entries(&self) -> &[elf::DynEntry] {
+        self.inner
+    }
+}
+
+/// The elf class, the `EI_CLASS` byte of the elf identification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfClass {
+    /// ELFCLASS32, 32 bit objects
+    Elf32,
+    /// ELFCLASS64, 64 bit objects
+    Elf64,
+    /// Any other raw class
+    Other(u8),
+}
+
+impl From<u8> for ElfClass {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Elf32,
+            2 => Self::Elf64,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Object file type enumeration, the `e_type` of an elf header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfType {
+    /// ET_NONE
+    None,
+    /// ET_REL, a relocatable object file
+    Relocatable,
+    /// ET_EXEC, an executable linked to run at a fixed address
+    Executable,
+    /// ET_DYN, a shared object or a position independent executable
+    SharedObject,
+    /// ET_CORE, a core dump
+    Core,
+    /// Any other raw type, typically OS or processor specific
+    Other(u16),
+}
+
+impl From<u16> for ElfType {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => Self::None,
+            1 => Self::Relocatable,
+            2 => Self::Executable,
+            3 => Self::SharedObject,
+            4 => Self::Core,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Target architecture enumeration, the `e_machine` of an elf header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Machine {
+    /// EM_386
+    X86,
+    /// EM_ARM
+    Arm,
+    /// EM_X86_64
+    X86_64,
+    /// EM_AARCH64
+    AArch64,
+    /// EM_RISCV
+    RiscV,
+    /// Any other raw machine
+    Other(u16),
+}
+
+impl From<u16> for Machine {
+    fn from(value: u16) -> Self {
+        match value {
+            3 => Self::X86,
+            40 => Self::Arm,
+            62 => Self::X86_64,
+            183 => Self::AArch64,
+            243 => Self::RiscV,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// An Elf File Header, as mapped at the start of a loaded module
+pub struct ElfHeader<'a> {
+    inner: &'a elf::FileHeader,
 }
 
+impl ElfHeader<'_> {
+    /// Access the elf class, whether the object is 32 or 64 bit
+    pub fn class(&self) -> ElfClass {
+        ElfClass::from(self.inner.e_ident[4])
+    }
+
+    /// Is the object encoded little endian, the `EI_DATA` byte of the elf identification
+    pub fn is_little_endian(&self) -> bool {
+        self.inner.e_ident[5] == 1
+    }
+
+    /// Access the object's raw type
+    pub fn raw_type(&self) -> elf::Half {
+        self.inner.e_type
+    }
+
+    /// Access the object's type
+    pub fn file_type(&self) -> ElfType {
+        ElfType::from(self.inner.e_type)
+    }
+
+    /// Is the object loaded at an address of the dynamic linker's choosing, as shared objects and PIE executables are
+    pub fn is_position_independent(&self) -> bool {
+        self.file_type() == ElfType::SharedObject
+    }
+
+    /// Access the object's raw target architecture
+    pub fn raw_machine(&self) -> elf::Half {
+        self.inner.e_machine
+    }
+
+    /// Access the object's target architecture
+    pub fn machine(&self) -> Machine {
+        Machine::from(self.inner.e_machine)
+    }
+
+    /// Access the processor specific flags
+    pub fn flags(&self) -> elf::Word {
+        self.inner.e_flags
+    }
+
+    /// Virtual address of the entry point, 0 if the object has none
+    pub fn entry_point(&self) -> usize {
+        self.inner.e_entry as usize
+    }
+
+    /// Number of program headers the object declares
+    pub fn program_header_count(&self) -> usize {
+        self.inner.e_phnum as usize
+    }
+}
+
+/// Segment type enumeration, the `p_type` of a program header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SegmentType {
+    /// PT_NULL, an unused entry
+    Null,
+    /// PT_LOAD, a segment mapped from the file
+    Load,
+    /// PT_DYNAMIC, the dynamic section
+    Dynamic,
+    /// PT_INTERP, the path of the dynamic linker the executable asks for
+    Interp,
+    /// PT_NOTE, auxiliary notes such as the build id
+    Note,
+    /// PT_SHLIB, reserved
+    Shlib,
+    /// PT_PHDR, the program headers themselves
+    Phdr,
+    /// PT_TLS, the thread local storage template
+    Tls,
+    /// PT_GNU_EH_FRAME, the `.eh_frame_hdr` unwinding lookup table
+    GnuEhFrame,
+    /// PT_GNU_STACK, whose flags request an executable stack or not
+    GnuStack,
+    /// PT_GNU_RELRO, made read only once relocations are applied
+    GnuRelro,
+    /// PT_GNU_PROPERTY, the `.note.gnu.property` section
+    GnuProperty,
+    /// PT_ARM_EXIDX, the ARM exception index table of EM_ARM modules
+    ArmExidx,
+    /// Any other raw segment type, typically OS or processor specific
+    Other(u32),
+}
+
+impl From<u32> for SegmentType {
+    fn from(value: u32) -> Self {
+        match value {
+            0 => Self::Null,
+            1 => Self::Load,
+            2 => Self::Dynamic,
+            3 => Self::Interp,
+            4 => Self::Note,
+            5 => Self::Shlib,
+            6 => Self::Phdr,
+            7 => Self::Tls,
+            0x6474e550 => Self::GnuEhFrame,
+            0x6474e551 => Self::GnuStack,
+            0x6474e552 => Self::GnuRelro,
+            0x6474e553 => Self::GnuProperty,
+            // Processor specific types depend on the machine, see `for_machine`
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl SegmentType {
+    /// Decode the raw type of a segment of a module built for `machine`, which gives processor specific types their meaning
+    pub fn for_machine(value: u32, machine: Option<Machine>) -> Self {
+        match (machine, value) {
+            (Some(Machine::Arm), 0x70000001) => Self::ArmExidx,
+            _ => Self::from(value),
+        }
+    }
+}
+
+/// Segment is executable
+const PF_X: elf::Word = 1;
+/// Segment is writable
+const PF_W: elf::Word = 2;
+/// Segment is readable
+const PF_R: elf::Word = 4;
+
 /// An Elf Program Header
 /// Primary examples are PT_LOAD and PT_DYNAMIC
 pub struct ProgramHeader<'a> {
     inner: &'a elf::ProgramHeader,
+    machine: Option<Machine>,
 }
 
 impl ProgramHeader<'_> {
-    /// Access the program headers type
+    /// Access the program headers raw type
     pub fn header_type(&self) -> elf::Word {
         self.inner.p_type
     }
 
+    /// Access the program headers type
+    pub fn segment_type(&self) -> SegmentType {
+        SegmentType::for_machine(self.inner.p_type, self.machine)
+    }
+
+    /// Access the program headers raw PF_* flags
+    pub fn flags(&self) -> elf::Word {
+        self.inner.p_flags
+    }
+
+    /// Is the segment mapped readable
+    pub fn is_readable(&self) -> bool {
+        self.inner.p_flags & PF_R != 0
+    }
+
+    /// Is the segment mapped writable
+    pub fn is_writable(&self) -> bool {
+        self.inner.p_flags & PF_W != 0
+    }
+
+    /// Is the segment mapped executable
+    pub fn is_executable(&self) -> bool {
+        self.inner.p_flags & PF_X != 0
+    }
+
+    /// The alignment the segment is mapped with, in memory and in the file
+    pub fn alignment(&self) -> usize {
+        self.inner.p_align as usize
+    }
+
     /// Access the program headers virtual address
     pub fn virtual_addr(&self) -> usize {
         self.inner.p_vaddr as usize
     }
 
+    /// Where the segment lies in the process, given the `base` address its module is loaded at
+    pub fn absolute_addr(&self, base: usize) -> usize {
+        base.wrapping_add(self.virtual_addr())
+    }
+
+    /// The addresses the segment covers in memory, given the `base` address its module is loaded at
+    pub fn absolute_range(&self, base: usize) -> Range<usize> {
+        let start = self.absolute_addr(base);
+        start..start.saturating_add(self.memory_size())
+    }
+
     /// Total size in memory
     pub fn memory_size(&self) -> usize {
         self.inner.p_memsz as usize
@@ -224,11 +618,136 @@ impl ProgramHeader<'_> {
 }
 
 /// A dynamic libraries plt maybe be addend entries or non addend entries
+#[derive(Clone, Copy)]
 pub enum RelocationTable<'a> {
     WithAddend(DynamicAddendRelocations<'a>),
     WithoutAddend(DynamicRelocations<'a>),
 }
 
+/// Architecture independent classification of a relocation's type
+/// The raw values differ per architecture, e.g. a JUMP_SLOT is `7` on x86_64 but `1026` on aarch64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocationKind {
+    /// R_*_NONE
+    None,
+    /// Word sized absolute symbol address, R_X86_64_64 / R_386_32 / R_AARCH64_ABS64 / R_ARM_ABS32
+    Absolute,
+    /// The executable holds a copy of a shared library's data object
+    Copy,
+    /// A GOT entry referencing a symbol, used by `-fno-plt` call sites and address taken functions
+    GlobDat,
+    /// A PLT GOT entry, the classic lazily bound function slot
+    JumpSlot,
+    /// Base relative adjustment with no symbol
+    Relative,
+    /// Indirect function, the slot is filled by calling a resolver
+    IRelative,
+    /// Any other raw relocation type, typically TLS
+    Other(u32),
+}
+
+impl RelocationKind {
+    /// Slots of this kind hold the address of a function imported by name and can be redirected with `patch`
+    pub fn is_hookable(&self) -> bool {
+        matches!(self, Self::JumpSlot | Self::GlobDat)
+    }
+}
+
+/// The table a relocation was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelocationSource {
+    /// DT_REL / DT_RELA, typically `.rel.dyn` or `.rela.dyn`
+    Dynamic,
+    /// DT_JMPREL, typically `.rel.plt` or `.rela.plt`
+    Plt,
+}
+
+/// A single relocation entry, normalized across addend and addendless tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relocation {
+    offset: usize,
+    symbol_index: usize,
+    raw_type: u32,
+    addend: Option<isize>,
+    source: RelocationSource,
+}
+
+impl Relocation {
+    fn from_rel(rel: &elf::DynRel, source: RelocationSource) -> Self {
+        Self {
+            offset: rel.r_offset as usize,
+            symbol_index: rel.symbol_index() as usize,
+            raw_type: rel.symbol_type(),
+            addend: None,
+            source,
+        }
+    }
+
+    fn from_rela(rela: &elf::DynRela, source: RelocationSource) -> Self {
+        Self {
+            offset: rela.r_offset as usize,
+            symbol_index: rela.symbol_index() as usize,
+            raw_type: rela.symbol_type(),
+            addend: Some(rela.r_addend as isize),
+            source,
+        }
+    }
+
+    /// The relocated slot's offset from the module's base address
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Index of the referenced symbol in the dynamic symbol table, zero if there is none
+    pub fn symbol_index(&self) -> usize {
+        self.symbol_index
+    }
+
+    /// The architecture specific relocation type
+    pub fn raw_type(&self) -> u32 {
+        self.raw_type
+    }
+
+    /// The architecture independent relocation kind
+    pub fn kind(&self) -> RelocationKind {
+        RelocationKind::from(self.raw_type)
+    }
+
+    /// The explicit addend, only available for relocations read from an addend table
+    pub fn addend(&self) -> Option<isize> {
+        self.addend
+    }
+
+    /// The table this relocation was read from
+    pub fn source(&self) -> RelocationSource {
+        self.source
+    }
+}
+
+/// A relocation picked by a `SymbolSelector`, with its symbol's resolved name and version
+#[derive(Debug)]
+pub struct SelectedRelocation<'a> {
+    pub relocation: Relocation,
+    pub name: &'a CStr,
+    pub version: Option<&'a CStr>,
+}
+
+/// Some linkers emit DT_RELSZ / DT_RELASZ covering the plt relocations as well.
+/// Splits `outer` around `inner` if `inner` lies entirely within it, so the same entries are not visited twice.
+fn exclude_slice<'s, T>(outer: &'s [T], inner: &[T]) -> (&'s [T], &'s [T]) {
+    let outer_range = outer.as_ptr_range();
+    let inner_range = inner.as_ptr_range();
+    if inner.is_empty()
+        || inner_range.start < outer_range.start
+        || inner_range.end > outer_range.end
+    {
+        return (outer, &[]);
+    }
+
+    let start = (inner_range.start as usize - outer_range.start as usize) / size_of::<T>();
+    (&outer[..start], &outer[start + inner.len()..])
+}
+
 /// Dynamic Library Entry
 /// An 'upgraded' LibraryEntry with the dynamic section resolved.
 pub struct DynamicLibrary<'a> {
@@ -240,6 +759,145 @@ pub struct DynamicLibrary<'a> {
     dyn_relocs: Option<DynamicRelocations<'a>>,
     dyn_addend_relocs: Option<DynamicAddendRelocations<'a>>,
     dyn_plt: Option<RelocationTable<'a>>,
+    dyn_versions: Option<SymbolVersions<'a>>,
+}
+
+/// The tables of a parsed dynamic section.
+/// They point into the module's memory rather than at the `LoadedLibrary` describing it,
+/// so they can be kept for a module and attached to a later view of it.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy)]
+pub(crate) struct DynamicTables<'a> {
+    dyn_section: DynamicSection<'a>,
+    dyn_string_table: StringTable<'a>,
+    dyn_symbols: Option<DynamicSymbols<'a>>,
+    dyn_relocs: Option<DynamicRelocations<'a>>,
+    dyn_addend_relocs: Option<DynamicAddendRelocations<'a>>,
+    dyn_plt: Option<RelocationTable<'a>>,
+    dyn_versions: Option<SymbolVersions<'a>>,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Debug for DynamicTables<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynamicTables")
+            .field("dyn_section", &self.dyn_section.entries().as_ptr())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Rebase a dynamic section address value into an absolute address.
+/// ET_EXEC executables run where they were linked, so their values are already absolute.
+/// Anything else holds virtual addresses, which glibc relocates in place for most modules while musl, bionic
+/// and the vDSO are left untouched. The value is taken as absolute when it already lies within one of the
+/// module's PT_LOAD segments, otherwise it is rebased by the load bias.
+fn rebase(lib: &LoadedLibrary<'_>, d_val_ptr: usize) -> usize {
+    let executable = lib
+        .elf_header()
+        .is_some_and(|header| header.file_type() == ElfType::Executable);
+    if executable || lib.contains_addr(d_val_ptr) {
+        return d_val_ptr;
+    }
+    lib.addr().wrapping_add(d_val_ptr)
+}
+
+/// View `count` entries at `addr` as a slice, if they are aligned and lie within one of the library's PT_LOAD segments
+fn checked_slice<'b, T>(lib: &LoadedLibrary<'_>, addr: usize, count: usize) -> Option<&'b [T]> {
+    if count == 0 {
+        return Some(&[]);
+    }
+    let size = count.checked_mul(size_of::<T>())?;
+    let ptr = addr as *const T;
+    match !ptr.is_null() && ptr.is_aligned() && lib.contains_range(addr, size) {
+        true => Some(unsafe { core::slice::from_raw_parts(ptr, count) }),
+        false => None,
+    }
+}
+
+/// The value of `tag`, required alongside `dependent`
+fn dependent_value(
+    dynamic_section: &DynamicSection<'_>,
+    dependent: DynamicSectionType,
+    tag: DynamicSectionType,
+) -> Result<usize, DynamicError> {
+    Ok(dynamic_section
+        .find_section(tag)
+        .ok_or(DynamicError::DependentSection(dependent, tag))?
+        .d_val_ptr as usize)
+}
+
+/// Check an entry size declared by `tag` matches the size of the struct we read the entries as
+fn check_entry_size<T>(tag: DynamicSectionType, entry_size: usize) -> Result<(), DynamicError> {
+    match entry_size == size_of::<T>() {
+        true => Ok(()),
+        false => Err(DynamicError::EntrySize(tag, entry_size)),
+    }
+}
+
+/// Count the dynamic symbols through the symbol hash tables, the dynamic section does not record it directly.
+/// DT_HASH's chain is as long as the symbol table, DT_GNU_HASH has to be walked to its last symbol.
+fn count_dyn_symbols(
+    lib: &LoadedLibrary<'_>,
+    dynamic_section: &DynamicSection<'_>,
+) -> Result<Option<usize>, DynamicError> {
+    if let Some(hash) = dynamic_section.find_section(DynamicSectionType::DT_HASH) {
+        let header = checked_slice::<u32>(lib, rebase(lib, hash.d_val_ptr as usize), 2)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_HASH))?;
+        return Ok(Some(header[1] as usize));
+    }
+
+    let Some(gnu_hash) = dynamic_section.find_section(DynamicSectionType::DT_GNU_HASH) else {
+        return Ok(None);
+    };
+    let addr = rebase(lib, gnu_hash.d_val_ptr as usize);
+    let header = checked_slice::<u32>(lib, addr, 4)
+        .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_GNU_HASH))?;
+    let (bucket_count, symbol_offset, bloom_count) =
+        (header[0] as usize, header[1] as usize, header[2] as usize);
+
+    let buckets_addr = bloom_count
+        .checked_mul(size_of::<usize>())
+        .and_then(|bloom_size| (addr + 4 * size_of::<u32>()).checked_add(bloom_size))
+        .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_GNU_HASH))?;
+    let buckets = checked_slice::<u32>(lib, buckets_addr, bucket_count)
+        .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_GNU_HASH))?;
+    let Some(last_bucket) = buckets
+        .iter()
+        .copied()
+        .max()
+        .filter(|b| *b as usize >= symbol_offset)
+    else {
+        // Only the unhashed symbols preceding the hashed ones
+        return Ok(Some(symbol_offset));
+    };
+
+    // Follow the last bucket's chain until the entry flagged as its end
+    let chain_addr = buckets_addr + bucket_count * size_of::<u32>();
+    let mut symbol = last_bucket as usize;
+    loop {
+        let entry_addr = (symbol - symbol_offset)
+            .checked_mul(size_of::<u32>())
+            .and_then(|offset| chain_addr.checked_add(offset));
+        let entry = entry_addr
+            .and_then(|entry_addr| checked_slice::<u32>(lib, entry_addr, 1))
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_GNU_HASH))?;
+        if entry[0] & 1 != 0 {
+            return Ok(Some(symbol + 1));
+        }
+        symbol += 1;
+    }
+}
+
+/// Hand a table's parse failure to `on_error`, which either passes the error on or leaves the table unavailable
+fn recover<T>(
+    result: Result<Option<T>, DynamicError>,
+    table: DynamicSectionType,
+    on_error: &mut impl FnMut(DynamicSectionType, DynamicError) -> Result<(), DynamicError>,
+) -> Result<Option<T>, DynamicError> {
+    match result {
+        Ok(parsed) => Ok(parsed),
+        Err(error) => on_error(table, error).map(|_| None),
+    }
 }
 
 /// Access the libraries dynamic symbols through the library's dynamic section
@@ -253,25 +911,24 @@ fn extract_dyn_symbols<'a, 'b>(
     };
 
     // We use explicit Elf Dynamic entry structs.
-    // The SYMENT size doesn't seem relevant anymore, so we can assert it the same size as the dyn entry to combat any egregious misusages
-    let table_size = dynamic_section
-        .find_section(DynamicSectionType::DT_SYMENT)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_SYMTAB,
-            DynamicSectionType::DT_SYMENT,
-        ))?
-        .d_val_ptr as usize;
-    assert_eq!(table_size, size_of::<elf::DynSym>());
-
-    // We don't have enough information to tell if this elf represents an Object Mapped or Shared Library / Executable mapped entry
-    // For object mapped the ptr's are relative. So we have to rebase by the virtual address from dl_info
-    let dyn_sym_ptr = match dyn_symbol_table.d_val_ptr as usize <= lib.addr() {
-        false => dyn_symbol_table.d_val_ptr as usize,
-        true => dyn_symbol_table.d_val_ptr as usize + lib.addr(),
-    } as *const elf::DynSym;
+    // The SYMENT size doesn't seem relevant anymore, so we can check it the same size as the dyn entry to combat any egregious misusages
+    let entry_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_SYMTAB,
+        DynamicSectionType::DT_SYMENT,
+    )?;
+    check_entry_size::<elf::DynSym>(DynamicSectionType::DT_SYMENT, entry_size)?;
+
+    // Without a hash table the best bound left is the end of the segment holding the symbols
+    let addr = rebase(lib, dyn_symbol_table.d_val_ptr as usize);
+    let count = match count_dyn_symbols(lib, dynamic_section)? {
+        Some(count) => count,
+        None => lib.segment_remaining(addr).unwrap_or_default() / size_of::<elf::DynSym>(),
+    };
 
     Ok(Some(DynamicSymbols {
-        inner: unsafe { dyn_sym_ptr.as_ref().unwrap() },
+        inner: checked_slice(lib, addr, count)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_SYMTAB))?,
     }))
 }
 
@@ -281,13 +938,14 @@ fn extract_dyn_section<'a, 'b>(
 ) -> Result<DynamicSection<'b>, DynamicError> {
     let dynamic_header = lib
         .program_headers()
-        .find(|p_h| p_h.header_type() == 0x02)
+        .find(|p_h| p_h.segment_type() == SegmentType::Dynamic)
         .ok_or(DynamicError::ProgramHeader)?;
 
-    let dynamic_sections = lib.addr() + dynamic_header.virtual_addr();
-    let dynamic_sections = dynamic_sections as *const elf::DynEntry;
+    let dynamic_sections = dynamic_header.absolute_addr(lib.addr());
+    let count = dynamic_header.memory_size() / size_of::<elf::DynEntry>();
     Ok(DynamicSection {
-        inner: unsafe { dynamic_sections.as_ref().unwrap() },
+        inner: checked_slice(lib, dynamic_sections, count)
+            .ok_or(DynamicError::DynamicSectionBounds)?,
     })
 }
 
@@ -299,23 +957,16 @@ fn extract_dyn_string_table<'a, 'b>(
     let str_table_entry = dynamic_section
         .find_section(DynamicSectionType::DT_STRTAB)
         .ok_or(DynamicError::RequiredSection(DynamicSectionType::DT_STRTAB))?;
-    let table_size = dynamic_section
-        .find_section(DynamicSectionType::DT_STRSZ)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_STRTAB,
-            DynamicSectionType::DT_STRSZ,
-        ))?
-        .d_val_ptr as usize;
-
-    // We don't have enough information to tell if this elf represents an Object Mapped or Shared Library / Executable mapped entry
-    // For object mapped the ptr's are relative. So we have to rebase by the virtual address from dl_info
-    let str_table_ptr = match str_table_entry.d_val_ptr as usize <= lib.addr() {
-        false => str_table_entry.d_val_ptr as usize,
-        true => str_table_entry.d_val_ptr as usize + lib.addr(),
-    } as *const libc::c_char;
+    let table_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_STRTAB,
+        DynamicSectionType::DT_STRSZ,
+    )?;
 
+    let str_table_ptr = rebase(lib, str_table_entry.d_val_ptr as usize);
     Ok(StringTable {
-        raw: unsafe { core::slice::from_raw_parts(str_table_ptr, table_size) },
+        raw: checked_slice(lib, str_table_ptr, table_size)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_STRTAB))?,
     })
 }
 
@@ -328,33 +979,23 @@ fn extract_dyn_relocs<'a, 'b>(
         return Ok(None);
     };
 
-    let total_size = dynamic_section
-        .find_section(DynamicSectionType::DT_RELSZ)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_REL,
-            DynamicSectionType::DT_RELSZ,
-        ))?
-        .d_val_ptr as usize;
-    let entry_size = dynamic_section
-        .find_section(DynamicSectionType::DT_RELENT)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_REL,
-            DynamicSectionType::DT_RELENT,
-        ))?
-        .d_val_ptr as usize;
-
-    assert_eq!(entry_size, size_of::<elf::DynRel>());
+    let total_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_REL,
+        DynamicSectionType::DT_RELSZ,
+    )?;
+    let entry_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_REL,
+        DynamicSectionType::DT_RELENT,
+    )?;
+    check_entry_size::<elf::DynRel>(DynamicSectionType::DT_RELENT, entry_size)?;
 
     let entry_count = total_size / entry_size;
-    // We don't have enough information to tell if this elf represents an Object Mapped or Shared Library / Executable mapped entry
-    // For object mapped the ptr's are relative. So we have to rebase by the virtual address from dl_info
-    let dyn_rel_entry = match dyn_rel_entry.d_val_ptr as usize <= lib.addr() {
-        false => dyn_rel_entry.d_val_ptr as usize,
-        true => dyn_rel_entry.d_val_ptr as usize + lib.addr(),
-    } as *const elf::DynRel;
-
+    let dyn_rel_entry = rebase(lib, dyn_rel_entry.d_val_ptr as usize);
     Ok(Some(DynamicRelocations {
-        inner: unsafe { core::slice::from_raw_parts(dyn_rel_entry, entry_count) },
+        inner: checked_slice(lib, dyn_rel_entry, entry_count)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_REL))?,
     }))
 }
 
@@ -367,33 +1008,23 @@ fn extract_dyn_addend_relocs<'a, 'b>(
         return Ok(None);
     };
 
-    let total_size = dynamic_section
-        .find_section(DynamicSectionType::DT_RELASZ)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_RELA,
-            DynamicSectionType::DT_RELASZ,
-        ))?
-        .d_val_ptr as usize;
-    let entry_size = dynamic_section
-        .find_section(DynamicSectionType::DT_RELAENT)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_RELA,
-            DynamicSectionType::DT_RELAENT,
-        ))?
-        .d_val_ptr as usize;
-
-    assert_eq!(entry_size, size_of::<elf::DynRela>());
+    let total_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_RELA,
+        DynamicSectionType::DT_RELASZ,
+    )?;
+    let entry_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_RELA,
+        DynamicSectionType::DT_RELAENT,
+    )?;
+    check_entry_size::<elf::DynRela>(DynamicSectionType::DT_RELAENT, entry_size)?;
 
     let entry_count = total_size / entry_size;
-    // We don't have enough information to tell if this elf represents an Object Mapped or Shared Library / Executable mapped entry
-    // For object mapped the ptr's are relative. So we have to rebase by the virtual address from dl_info
-    let dyn_rel_entry = match dyn_rel_entry.d_val_ptr as usize <= lib.addr() {
-        false => dyn_rel_entry.d_val_ptr as usize,
-        true => dyn_rel_entry.d_val_ptr as usize + lib.addr(),
-    } as *const elf::DynRela;
-
+    let dyn_rel_entry = rebase(lib, dyn_rel_entry.d_val_ptr as usize);
     Ok(Some(DynamicAddendRelocations {
-        inner: unsafe { core::slice::from_raw_parts(dyn_rel_entry, entry_count) },
+        inner: checked_slice(lib, dyn_rel_entry, entry_count)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_RELA))?,
     }))
 }
 
@@ -416,34 +1047,25 @@ fn extract_dyn_plt<'a, 'b>(
             DynamicSectionType::DT_PLTREL,
             DynamicSectionType::DT_JMPREL,
         ))?;
-    let total_size = dynamic_section
-        .find_section(DynamicSectionType::DT_PLTRELSZ)
-        .ok_or(DynamicError::DependentSection(
-            DynamicSectionType::DT_PLTREL,
-            DynamicSectionType::DT_PLTRELSZ,
-        ))?
-        .d_val_ptr as usize;
-
-    let entry_addr = match dyn_plt_entry.d_val_ptr as usize <= lib.addr() {
-        false => dyn_plt_entry.d_val_ptr as usize,
-        true => dyn_plt_entry.d_val_ptr as usize + lib.addr(),
-    };
-
+    let total_size = dependent_value(
+        dynamic_section,
+        DynamicSectionType::DT_PLTREL,
+        DynamicSectionType::DT_PLTRELSZ,
+    )?;
+
+    let entry_addr = rebase(lib, dyn_plt_entry.d_val_ptr as usize);
+    let out_of_bounds = DynamicError::OutOfBounds(DynamicSectionType::DT_JMPREL);
     Ok(match relocation_type {
         DynamicSectionType::DT_REL => {
             let entry_count = total_size / size_of::<elf::DynRel>();
             Some(RelocationTable::WithoutAddend(DynamicRelocations {
-                inner: unsafe {
-                    core::slice::from_raw_parts(entry_addr as *const elf::DynRel, entry_count)
-                },
+                inner: checked_slice(lib, entry_addr, entry_count).ok_or(out_of_bounds)?,
             }))
         }
         DynamicSectionType::DT_RELA => {
             let entry_count = total_size / size_of::<elf::DynRela>();
             Some(RelocationTable::WithAddend(DynamicAddendRelocations {
-                inner: unsafe {
-                    core::slice::from_raw_parts(entry_addr as *const elf::DynRela, entry_count)
-                },
+                inner: checked_slice(lib, entry_addr, entry_count).ok_or(out_of_bounds)?,
             }))
         }
         _ => None,
@@ -455,12 +1077,75 @@ impl<'a> DynamicLibrary<'a> {
     /// The Dynamic Library will take ownership of the load library as well as store
     /// all relevant dynamic sections for easy access and symbol resolution
     pub fn initialize(lib: LoadedLibrary<'a>) -> Result<Self, DynamicError> {
+        Self::parse(lib, |_, error| Err(error))
+    }
+
+    /// Consume a LoadedLibrary and parse every table of its dynamic section which can be parsed.
+    /// A malformed table is left unavailable, as if the library did not have it, and reported to `on_warning`.
+    /// A library without a string table is given an empty one.
+    /// Only fails if the dynamic section itself can not be found.
+    pub fn initialize_lenient<F>(
+        lib: LoadedLibrary<'a>,
+        mut on_warning: F,
+    ) -> Result<Self, DynamicError>
+    where
+        F: FnMut(ParseWarning),
+    {
+        Self::parse(lib, |table, error| {
+            on_warning(ParseWarning { table, error });
+            Ok(())
+        })
+    }
+
+    /// Parse leniently like `initialize_lenient`, collecting the warnings
+    #[cfg(feature = "alloc")]
+    pub fn initialize_with_warnings(
+        lib: LoadedLibrary<'a>,
+    ) -> Result<(Self, Vec<ParseWarning>), DynamicError> {
+        let mut warnings = Vec::new();
+        let dyn_lib = Self::initialize_lenient(lib, |warning| warnings.push(warning))?;
+        Ok((dyn_lib, warnings))
+    }
+
+    /// Parse every table, handing the failure of any table apart from the dynamic section to `on_error`.
+    /// The table is left unavailable unless `on_error` passes the error on.
+    fn parse(
+        lib: LoadedLibrary<'a>,
+        mut on_error: impl FnMut(DynamicSectionType, DynamicError) -> Result<(), DynamicError>,
+    ) -> Result<Self, DynamicError> {
         let dyn_section = extract_dyn_section(&lib)?;
-        let dyn_string_table = extract_dyn_string_table(&lib, &dyn_section)?;
-        let dyn_symbols = extract_dyn_symbols(&lib, &dyn_section)?;
-        let dyn_relocs = extract_dyn_relocs(&lib, &dyn_section)?;
-        let dyn_addend_relocs = extract_dyn_addend_relocs(&lib, &dyn_section)?;
-        let dyn_plt = extract_dyn_plt(&lib, &dyn_section)?;
+        let dyn_string_table = recover(
+            extract_dyn_string_table(&lib, &dyn_section).map(Some),
+            DynamicSectionType::DT_STRTAB,
+            &mut on_error,
+        )?
+        .unwrap_or(StringTable { raw: &[] });
+        let dyn_symbols = recover(
+            extract_dyn_symbols(&lib, &dyn_section),
+            DynamicSectionType::DT_SYMTAB,
+            &mut on_error,
+        )?;
+        let dyn_relocs = recover(
+            extract_dyn_relocs(&lib, &dyn_section),
+            DynamicSectionType::DT_REL,
+            &mut on_error,
+        )?;
+        let dyn_addend_relocs = recover(
+            extract_dyn_addend_relocs(&lib, &dyn_section),
+            DynamicSectionType::DT_RELA,
+            &mut on_error,
+        )?;
+        let dyn_plt = recover(
+            extract_dyn_plt(&lib, &dyn_section),
+            DynamicSectionType::DT_JMPREL,
+            &mut on_error,
+        )?;
+        let symbol_count = dyn_symbols.as_ref().map_or(0, |s| s.entries().len());
+        let dyn_versions = recover(
+            version::extract_symbol_versions(&lib, &dyn_section, symbol_count),
+            DynamicSectionType::DT_VERSYM,
+            &mut on_error,
+        )?;
 
         Ok(Self {
             library: lib,
@@ -470,9 +1155,39 @@ impl<'a> DynamicLibrary<'a> {
             dyn_relocs,
             dyn_addend_relocs,
             dyn_plt,
+            dyn_versions,
         })
     }
 
+    /// Split off the parsed tables, to attach them to another view of the same module with `from_tables`
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_tables(self) -> DynamicTables<'a> {
+        DynamicTables {
+            dyn_section: self.dyn_section,
+            dyn_string_table: self.dyn_string_table,
+            dyn_symbols: self.dyn_symbols,
+            dyn_relocs: self.dyn_relocs,
+            dyn_addend_relocs: self.dyn_addend_relocs,
+            dyn_plt: self.dyn_plt,
+            dyn_versions: self.dyn_versions,
+        }
+    }
+
+    /// View `lib` through tables parsed from the same module, without parsing its dynamic section again
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_tables(lib: LoadedLibrary<'a>, tables: DynamicTables<'a>) -> Self {
+        Self {
+            library: lib,
+            dyn_section: tables.dyn_section,
+            dyn_string_table: tables.dyn_string_table,
+            dyn_symbols: tables.dyn_symbols,
+            dyn_relocs: tables.dyn_relocs,
+            dyn_addend_relocs: tables.dyn_addend_relocs,
+            dyn_plt: tables.dyn_plt,
+            dyn_versions: tables.dyn_versions,
+        }
+    }
+
     /// Finding target function differs on 32 bit and 64 bit.
     /// On 32 bit we want to check the relocations table only, opposed to the addend relocations table.
     /// Additionally, we will fall back to the plt given it is an addendless relocation table.
@@ -485,11 +1200,10 @@ impl<'a> DynamicLibrary<'a> {
             if let Some(symbol) = dyn_relas
                 .flat_map(|e| {
                     dyn_symbols
-                        .resolve_name(e.symbol_index() as usize, string_table)
+                        .resolve_cstr(e.symbol_index() as usize, string_table)
                         .map(|s| (e, s))
                 })
-                .filter(|(_, s)| s.eq(symbol_name))
-                .next()
+                .find(|(e, s)| e.kind().is_hookable() && s.to_bytes() == symbol_name.as_bytes())
                 .map(|(target_function, _)| target_function)
             {
                 return Some(symbol);
@@ -501,11 +1215,10 @@ impl<'a> DynamicLibrary<'a> {
             if let Some(symbol) = dyn_relas
                 .flat_map(|e| {
                     dyn_symbols
-                        .resolve_name(e.symbol_index() as usize, string_table)
+                        .resolve_cstr(e.symbol_index() as usize, string_table)
                         .map(|s| (e, s))
                 })
-                .filter(|(_, s)| s.eq(symbol_name))
-                .next()
+                .find(|(e, s)| e.kind().is_hookable() && s.to_bytes() == symbol_name.as_bytes())
                 .map(|(target_function, _)| target_function)
             {
                 return Some(symbol);
@@ -526,10 +1239,10 @@ impl<'a> DynamicLibrary<'a> {
             if let Some(symbol) = dyn_relas
                 .flat_map(|e| {
                     symbols
-                        .resolve_name(e.symbol_index() as usize, string_table)
+                        .resolve_cstr(e.symbol_index() as usize, string_table)
                         .map(|s| (e, s))
                 })
-                .find(|(_, s)| s.eq(symbol_name))
+                .find(|(e, s)| e.kind().is_hookable() && s.to_bytes() == symbol_name.as_bytes())
                 .map(|(target_function, _)| target_function)
             {
                 return Some(symbol);
@@ -541,10 +1254,10 @@ impl<'a> DynamicLibrary<'a> {
             if let Some(symbol) = dyn_relas
                 .flat_map(|e| {
                     symbols
-                        .resolve_name(e.symbol_index() as usize, string_table)
+                        .resolve_cstr(e.symbol_index() as usize, string_table)
                         .map(|s| (e, s))
                 })
-                .find(|(_, s)| s.eq(symbol_name))
+                .find(|(e, s)| e.kind().is_hookable() && s.to_bytes() == symbol_name.as_bytes())
                 .map(|(target_function, _)| target_function)
             {
                 return Some(symbol);
@@ -552,6 +1265,107 @@ impl<'a> DynamicLibrary<'a> {
         }
         None
     }
+    /// Iterate every relocation of the library.
+    /// The dynamic relocations are visited first, followed by the plt relocations.
+    pub fn relocations(&self) -> impl Iterator<Item = Relocation> + '_ {
+        let rels = self.relocs().map(|r| r.entries()).unwrap_or_default();
+        let relas = self
+            .addend_relocs()
+            .map(|r| r.entries())
+            .unwrap_or_default();
+        let (plt_rels, plt_relas) = match self.plt() {
+            Some(RelocationTable::WithoutAddend(r)) => (r.entries(), &[][..]),
+            Some(RelocationTable::WithAddend(r)) => (&[][..], r.entries()),
+            None => (&[][..], &[][..]),
+        };
+        let (rels_head, rels_tail) = exclude_slice(rels, plt_rels);
+        let (relas_head, relas_tail) = exclude_slice(relas, plt_relas);
+
+        let dynamic = rels_head
+            .iter()
+            .chain(rels_tail)
+            .map(|r| Relocation::from_rel(r, RelocationSource::Dynamic))
+            .chain(
+                relas_head
+                    .iter()
+                    .chain(relas_tail)
+                    .map(|r| Relocation::from_rela(r, RelocationSource::Dynamic)),
+            );
+        let plt = plt_rels
+            .iter()
+            .map(|r| Relocation::from_rel(r, RelocationSource::Plt))
+            .chain(
+                plt_relas
+                    .iter()
+                    .map(|r| Relocation::from_rela(r, RelocationSource::Plt)),
+            );
+        dynamic.chain(plt)
+    }
+
+    /// Iterate every relocation referencing the symbol `symbol_name`, regardless of its kind
+    pub fn find_relocations<'b>(
+        &'b self,
+        symbol_name: &'b str,
+    ) -> impl Iterator<Item = Relocation> + 'b {
+        let string_table = self.string_table();
+        let symbols = self.symbols();
+        self.relocations().filter(move |reloc| {
+            reloc.symbol_index() != 0
+                && symbols
+                    .and_then(|s| s.resolve_cstr(reloc.symbol_index(), string_table))
+                    .is_some_and(|name| name.to_bytes() == symbol_name.as_bytes())
+        })
+    }
+
+    /// Iterate the relocations referencing `symbol_name` whose slots can be redirected with `patch`.
+    /// This covers JUMP_SLOT plt entries as well as GLOB_DAT entries emitted for `-fno-plt` call sites
+    /// and address taken functions, skipping kinds such as COPY.
+    pub fn find_hookable_relocations<'b>(
+        &'b self,
+        symbol_name: &'b str,
+    ) -> impl Iterator<Item = Relocation> + 'b {
+        self.find_relocations(symbol_name)
+            .filter(|reloc| reloc.kind().is_hookable())
+    }
+
+    /// Iterate every relocation whose symbol is picked by `selector`, along with the symbol's resolved name and version.
+    /// Every relocation table is considered, filter on the relocation's kind to pick out hookable slots.
+    pub fn select_relocations<'b>(
+        &'b self,
+        selector: &'b SymbolSelector<'_>,
+    ) -> impl Iterator<Item = SelectedRelocation<'b>> + 'b {
+        let string_table = self.string_table();
+        let symbols = self.symbols();
+        let versions = self.versions();
+        self.relocations().filter_map(move |relocation| {
+            if relocation.symbol_index() == 0 {
+                return None;
+            }
+            let name = symbols?.resolve_cstr(relocation.symbol_index(), string_table)?;
+            let version = versions
+                .and_then(|v| v.resolve_version_cstr(relocation.symbol_index(), string_table));
+            selector
+                .matches(name.to_bytes(), version.map(CStr::to_bytes))
+                .then_some(SelectedRelocation {
+                    relocation,
+                    name,
+                    version,
+                })
+        })
+    }
+
+    /// Build an index of every symbol referencing relocation, for fast repeated lookups
+    #[cfg(feature = "alloc")]
+    pub fn build_index(&self) -> SymbolIndex<'_> {
+        SymbolIndex::build(self)
+    }
+
+    /// Absolute address of the slot `relocation` applies to.
+    /// Wraps around like the dynamic linker's own arithmetic, a corrupt offset yields an address outside the library.
+    pub fn relocation_addr(&self, relocation: &Relocation) -> usize {
+        self.base_addr().wrapping_add(relocation.offset())
+    }
+
     /// Access the plt as a dynamic relocation table if possible
     /// can fail if the plt is not available or the plt is with addend
     pub fn plt_rel(&self) -> Option<&DynamicRelocations<'_>> {
@@ -585,6 +1399,11 @@ impl<'a> DynamicLibrary<'a> {
         self.dyn_addend_relocs.as_ref()
     }
 
+    /// Access the dynamic libraries symbol versioning tables if available
+    pub fn versions(&self) -> Option<&SymbolVersions<'_>> {
+        self.dyn_versions.as_ref()
+    }
+
     /// Access the dynamic libraries symbol table if available
     pub fn symbols(&self) -> Option<&DynamicSymbols<'_>> {
         self.dyn_symbols.as_ref()
@@ -611,20 +1430,79 @@ impl<'a> DynamicLibrary<'a> {
     pub fn string_table(&self) -> &StringTable<'_> {
         &self.dyn_string_table
     }
+
+    /// The libraries DT_SONAME if it declares one, executables usually do not
+    pub fn soname(&self) -> Option<&CStr> {
+        let soname = self
+            .dyn_section
+            .find_section(DynamicSectionType::DT_SONAME)?;
+        self.dyn_string_table
+            .read_cstr_at(soname.d_val_ptr as usize)
+    }
+
+    /// Name of the defined dynamic symbol covering the absolute address `addr`.
+    /// A symbol starting exactly at `addr` is preferred, otherwise the first whose `st_size` bytes contain it.
+    /// Only the dynamic symbol table is available in memory, so local and stripped functions are not found.
+    pub fn symbol_at(&self, addr: usize) -> Option<&CStr> {
+        let symbols = self.symbols()?.entries();
+        let start = |symbol: &elf::DynSym| self.library.addr.wrapping_add(symbol.st_value as usize);
+        let defined = || {
+            (1..symbols.len()).filter(|&index| {
+                let symbol = &symbols[index];
+                symbol.is_defined() && symbol.st_value != 0 && symbol.st_name != 0
+            })
+        };
+        let index = defined()
+            .find(|&index| start(&symbols[index]) == addr)
+            .or_else(|| {
+                defined().find(|&index| {
+                    let offset = addr.wrapping_sub(start(&symbols[index]));
+                    offset < symbols[index].st_size as usize
+                })
+            })?;
+        self.dyn_symbols
+            .as_ref()?
+            .resolve_cstr(index, &self.dyn_string_table)
+    }
 }
 
 /// A library loaded in the process
+#[derive(Debug, Clone)]
 pub struct LoadedLibrary<'a> {
     addr: usize,
-    name: Cow<'a, str>,
+    name: &'a CStr,
     program_headers: &'a [elf::ProgramHeader],
 }
 
 impl<'a> LoadedLibrary<'a> {
+    /// Describe a module mapped at `addr` by hand, such as one the dynamic linker does not know about.
+    ///
+    /// # Safety
+    /// The program headers must describe memory mapped at `addr`, which has to stay mapped while the library,
+    /// or anything parsed from it, is in use.
+    pub unsafe fn from_raw_parts(
+        addr: usize,
+        name: &'a CStr,
+        program_headers: &'a [elf::ProgramHeader],
+    ) -> Self {
+        Self {
+            addr,
+            name,
+            program_headers,
+        }
+    }
+
     /// Access the libraries string name
     /// This is more the libraries `path` than the name per say
-    pub fn name(&self) -> &str {
-        &self.name
+    /// Paths which are not valid UTF-8 are converted lossily, see `name_cstr` for the raw path.
+    #[cfg(feature = "alloc")]
+    pub fn name(&self) -> Cow<'a, str> {
+        self.name.to_string_lossy()
+    }
+
+    /// Access the libraries raw null terminated path, as reported by the dynamic linker
+    pub fn name_cstr(&self) -> &'a CStr {
+        self.name
     }
 
     /// Access the libraries virtual address
@@ -634,23 +1512,152 @@ impl<'a> LoadedLibrary<'a> {
 
     /// Iterate the libraries program headers
     pub fn program_headers(&self) -> impl Iterator<Item = ProgramHeader<'_>> {
+        let machine = self.elf_header().map(|header| header.machine());
         self.program_headers
             .iter()
-            .map(|header| ProgramHeader { inner: header })
+            .map(move |header| ProgramHeader {
+                inner: header,
+                machine,
+            })
     }
 
     /// Access the libraries PT_INTERP program headers
     pub fn interpreter_header(&self) -> Option<ProgramHeader<'_>> {
-        self.program_headers().find(|p_h| p_h.header_type() == 0x03)
+        self.program_headers()
+            .find(|p_h| p_h.segment_type() == SegmentType::Interp)
+    }
+
+    /// The dynamic linker path the PT_INTERP segment asks for, usually only found in executables
+    pub fn interpreter_path(&self) -> Option<&CStr> {
+        let interpreter = self.interpreter_header()?;
+        let addr = interpreter.absolute_addr(self.addr);
+        let size = interpreter.file_size();
+        let bytes = checked_slice::<u8>(self, addr, size).filter(|bytes| !bytes.is_empty())?;
+        CStr::from_bytes_until_nul(bytes).ok()
     }
 
     /// Access the libraries PT_LOAD program headers
     pub fn load_headers(&self) -> impl Iterator<Item = ProgramHeader<'_>> {
         self.program_headers()
-            .filter(|p_h| p_h.header_type() == 0x01)
+            .filter(|p_h| p_h.segment_type() == SegmentType::Load)
+    }
+
+    /// Does `addr` fall within one of the libraries PT_LOAD segments
+    pub fn contains_addr(&self, addr: usize) -> bool {
+        self.load_headers()
+            .any(|p_h| p_h.absolute_range(self.addr).contains(&addr))
+    }
+
+    /// Does `[addr, addr + size)` fall entirely within one of the libraries PT_LOAD segments
+    pub fn contains_range(&self, addr: usize, size: usize) -> bool {
+        self.segment_remaining(addr)
+            .is_some_and(|remaining| size <= remaining)
+    }
+
+    /// How many bytes of the PT_LOAD segment containing `addr` lie from `addr` onwards
+    fn segment_remaining(&self, addr: usize) -> Option<usize> {
+        self.load_headers().find_map(|p_h| {
+            let start = p_h.absolute_addr(self.addr);
+            let end = start.checked_add(p_h.memory_size())?;
+            (start..end).contains(&addr).then(|| end - addr)
+        })
+    }
+
+    /// The protection of the page holding `addr` as loaded: that of its PT_LOAD segment, without PROT_WRITE on pages
+    /// PT_GNU_RELRO made read only. Addresses outside of the library are reported as PROT_READ.
+    ///
+    /// Only whole pages are protected: glibc and musl round the end of PT_GNU_RELRO down, leaving a last page shared
+    /// with `.got.plt` or `.data` writable, while bionic rounds it up.
+    pub(crate) fn page_protection(&self, addr: usize) -> libc::c_int {
+        let Some(segment) = self
+            .load_headers()
+            .find(|p_h| p_h.absolute_range(self.addr).contains(&addr))
+        else {
+            return libc::PROT_READ;
+        };
+        let mut prot = libc::PROT_NONE;
+        if segment.is_readable() {
+            prot |= libc::PROT_READ;
+        }
+        if segment.is_writable() {
+            prot |= libc::PROT_WRITE;
+        }
+        if segment.is_executable() {
+            prot |= libc::PROT_EXEC;
+        }
+
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGE_SIZE) as usize };
+        let page_start = |addr: usize| addr & !(page_size - 1);
+        let relro = self
+            .program_headers()
+            .filter(|p_h| p_h.segment_type() == SegmentType::GnuRelro)
+            .any(|p_h| {
+                let range = p_h.absolute_range(self.addr);
+                #[cfg(target_os = "android")]
+                let end = page_start(range.end.saturating_add(page_size - 1));
+                #[cfg(not(target_os = "android"))]
+                let end = page_start(range.end);
+                (page_start(range.start)..end).contains(&addr)
+            });
+        match relro {
+            true => prot & !libc::PROT_WRITE,
+            false => prot,
+        }
+    }
+
+    /// The elf header mapped at the start of the library's first segment.
+    /// Objects built to be loaded without their headers, such as some hand made modules, have none.
+    pub fn elf_header(&self) -> Option<ElfHeader<'_>> {
+        // Found from the raw headers, whose machine dependent types need the elf header
+        let segment = self
+            .program_headers
+            .iter()
+            .find(|p_h| SegmentType::from(p_h.p_type) == SegmentType::Load && p_h.p_offset == 0)?;
+        let addr = self.addr.wrapping_add(segment.p_vaddr as usize);
+        let header = addr as *const elf::FileHeader;
+        if !header.is_aligned() || size_of::<elf::FileHeader>() > segment.p_memsz as usize {
+            return None;
+        }
+
+        let header = unsafe { &*header };
+        (header.e_ident[..4] == *b"\x7fELF").then_some(ElfHeader { inner: header })
+    }
+
+    /// Classify the library using the auxiliary vector the kernel handed the process.
+    /// The main program's headers are reported through AT_PHDR, the vDSO's follow its AT_SYSINFO_EHDR elf header
+    /// and the dynamic linker is loaded at AT_BASE.
+    pub fn kind(&self) -> ModuleKind {
+        let phdr = self.program_headers.as_ptr() as usize;
+        let main_phdr = unsafe { libc::getauxval(libc::AT_PHDR) } as usize;
+        let vdso = unsafe { libc::getauxval(libc::AT_SYSINFO_EHDR) } as usize;
+        let interpreter = unsafe { libc::getauxval(libc::AT_BASE) } as usize;
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGE_SIZE) } as usize;
+
+        if phdr == main_phdr {
+            ModuleKind::MainProgram
+        } else if vdso != 0 && (vdso..vdso + page_size).contains(&phdr) {
+            ModuleKind::Vdso
+        } else if interpreter != 0 && self.addr == interpreter {
+            ModuleKind::DynamicLinker
+        } else {
+            ModuleKind::SharedObject
+        }
     }
 }
 
+/// The role a loaded module plays in the process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    /// The executable the process was started with
+    MainProgram,
+    /// The dynamic linker named by the main program's PT_INTERP
+    DynamicLinker,
+    /// The kernel provided virtual dynamic shared object
+    Vdso,
+    /// Any other shared object
+    SharedObject,
+}
+
 #[derive(Debug)]
 pub struct PatchError {
     addr: usize,
@@ -671,84 +1678,148 @@ impl Display for PatchError {
 impl Error for PatchError {}
 
 /// Attempts to patch plt entry at entry_addr.
-/// Sets page protections containing entry_addr to PROT_WRITE | PROT_EXEC before replacing the pointer.
-/// After writing out reverts the page to solely PROT_READ.
+/// Pages which are not writable as loaded are made writable for the write, then given back the protection of the
+/// module segment holding entry_addr, or solely PROT_READ when no loaded module holds it.
 /// Returns the previous value contained in the entry_addr prior to patching.
 pub fn patch(entry_addr: usize, func: usize) -> Result<usize, PatchError> {
-    let page_size = unsafe { libc::sysconf(libc::_SC_PAGE_SIZE) as usize };
-    let page_aligned_addr = ((entry_addr as usize / page_size) * page_size) as *mut c_void;
+    let prot = for_each_module(|library| match library.contains_addr(entry_addr) {
+        true => ControlFlow::Break(library.page_protection(entry_addr)),
+        false => ControlFlow::Continue(()),
+    })
+    .unwrap_or(libc::PROT_READ);
+    write_protected(entry_addr, func, prot)
+}
+
+/// Store `value` at `addr`, returning the value it held, where `prot` is the current protection of its page.
+/// Writable pages are swapped in place, others are made writable for the write and then given `prot` back.
+pub(crate) fn write_protected(
+    addr: usize,
+    value: usize,
+    prot: libc::c_int,
+) -> Result<usize, PatchError> {
+    let slot = unsafe { &*(addr as *const AtomicUsize) };
+    if prot & libc::PROT_WRITE != 0 {
+        return Ok(slot.swap(value, Ordering::AcqRel));
+    }
 
-    unsafe {
-        // Set the memory page to read, write
-        let prot_res = libc::mprotect(
-            page_aligned_addr,
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGE_SIZE) as usize };
+    let page_aligned_addr = (addr & !(page_size - 1)) as *mut c_void;
+    let set_protection = |prot| match unsafe { libc::mprotect(page_aligned_addr, page_size, prot) }
+    {
+        0 => Ok(()),
+        _ => Err(PatchError {
+            addr: page_aligned_addr as usize,
             page_size,
-            libc::PROT_WRITE | libc::PROT_READ,
-        );
-        if prot_res != 0 {
-            return Err(PatchError {
-                addr: page_aligned_addr as usize,
-                page_size,
-                prot: libc::PROT_WRITE | libc::PROT_EXEC,
-            });
-        }
+            prot,
+        }),
+    };
 
-        // Replace the function address
-        let previous_address = core::ptr::replace(entry_addr as *mut _, func as *mut c_void);
+    set_protection(prot | libc::PROT_WRITE)?;
+    let previous = slot.swap(value, Ordering::AcqRel);
+    set_protection(prot)?;
+    Ok(previous)
+}
 
-        // Set the memory page protection back to read only
-        let prot_res = libc::mprotect(page_aligned_addr, page_size, libc::PROT_READ);
-        if prot_res != 0 {
-            return Err(PatchError {
-                addr: page_aligned_addr as usize,
-                page_size,
-                prot: libc::PROT_READ,
-            });
-        }
+/// Drive `dl_iterate_phdr(3)` with a Rust closure, which may stop the iteration early
+fn iterate_phdr<B, F>(mut visitor: F) -> Option<B>
+where
+    F: FnMut(&libc::dl_phdr_info, usize) -> ControlFlow<B>,
+{
+    struct Visit<B, F> {
+        visitor: F,
+        result: Option<B>,
+    }
+
+    // Callback for `dl_iterate_phdr(3)`.
+    unsafe extern "C" fn visit_object<B, F>(
+        info: *mut libc::dl_phdr_info,
+        size: usize,
+        data: *mut libc::c_void,
+    ) -> libc::c_int
+    where
+        F: FnMut(&libc::dl_phdr_info, usize) -> ControlFlow<B>,
+    {
+        let visit = unsafe { &mut *(data as *mut Visit<B, F>) };
+        let Some(dl_info) = (unsafe { info.as_ref() }) else {
+            return 0;
+        };
 
-        Ok(previous_address as usize)
+        match (visit.visitor)(dl_info, size) {
+            ControlFlow::Continue(()) => 0,
+            ControlFlow::Break(result) => {
+                visit.result = Some(result);
+                1
+            }
+        }
     }
-}
 
-/// Returns a `Vec` of objects loaded into the current address space.
-pub fn collect_modules<'a>() -> Vec<LoadedLibrary<'a>> {
-    let mut ret = Vec::new();
+    let mut visit = Visit {
+        visitor: &mut visitor,
+        result: None,
+    };
+    let visit_p = &mut visit as *mut Visit<B, &mut F> as *mut libc::c_void;
+    unsafe { libc::dl_iterate_phdr(Some(visit_object::<B, &mut F>), visit_p) };
 
-    // Pushes an `Object` into the result vector on the behalf of C.
-    extern "C" fn push_object(objs: &mut Vec<LoadedLibrary>, dl_info: &libc::dl_phdr_info) {
-        let name = unsafe { core::ffi::CStr::from_ptr(dl_info.dlpi_name) }.to_string_lossy();
-        // We have to copy sthe `dl_phdr_info` struct out, as the same memory buffer is used for
-        // each entry during the iteration process. Otherwise we could have used a vector of
-        // pointers.
+    visit.result
+}
 
-        if dl_info.dlpi_phnum == 0 {
-            return;
+/// Visit every object loaded into the current address space without allocating.
+/// The visitor may stop the iteration early by returning `ControlFlow::Break`, whose value is returned.
+/// The library is only valid for the duration of the visit, the dynamic linker holds its lock meanwhile
+/// so the visitor must not load or unload libraries.
+pub fn for_each_module<B, F>(mut visitor: F) -> Option<B>
+where
+    F: FnMut(LoadedLibrary<'_>) -> ControlFlow<B>,
+{
+    iterate_phdr(|dl_info, _size| {
+        if dl_info.dlpi_phnum == 0 || dl_info.dlpi_phdr.is_null() {
+            return ControlFlow::Continue(());
         }
 
+        let name = match dl_info.dlpi_name.is_null() {
+            true => c"",
+            false => unsafe { CStr::from_ptr(dl_info.dlpi_name) },
+        };
         let program_headers =
             unsafe { core::slice::from_raw_parts(dl_info.dlpi_phdr, dl_info.dlpi_phnum as usize) };
-        objs.push(LoadedLibrary {
+        visitor(LoadedLibrary {
             addr: dl_info.dlpi_addr as usize,
             name,
             program_headers,
-        });
-    }
+        })
+    })
+}
 
-    // Callback for `dl_iterate_phdr(3)`.
-    unsafe extern "C" fn collect_objs(
-        info: *mut libc::dl_phdr_info,
-        _sz: usize,
-        data: *mut libc::c_void,
-    ) -> libc::c_int {
-        if let Some(info) = unsafe { info.as_ref() } {
-            push_object(&mut *(data as *mut Vec<LoadedLibrary>), info); // Get Rust to push the object.
-        };
+/// The link map's generation, the total count of objects the dynamic linker has loaded and unloaded.
+/// It only ever grows, and changes whenever the set of loaded modules does.
+/// Returns None if the dynamic linker does not report `dlpi_adds` and `dlpi_subs`, such as Android before R.
+pub fn link_map_generation() -> Option<u64> {
+    link_map_counters().map(|(adds, subs)| adds.wrapping_add(subs))
+}
 
-        0
-    }
+/// The dynamic linker's `dlpi_adds` and `dlpi_subs`, the count of objects loaded and unloaded so far
+pub(crate) fn link_map_counters() -> Option<(u64, u64)> {
+    iterate_phdr(|dl_info, size| {
+        let counted =
+            core::mem::offset_of!(libc::dl_phdr_info, dlpi_subs) + size_of::<libc::c_ulonglong>();
+        ControlFlow::Break(match size >= counted {
+            true => Some((dl_info.dlpi_adds, dl_info.dlpi_subs)),
+            false => None,
+        })
+    })
+    .flatten()
+}
 
-    let ret_void_p = &mut ret as *mut Vec<LoadedLibrary> as *mut libc::c_void;
-    unsafe { libc::dl_iterate_phdr(Some(collect_objs), ret_void_p) };
+/// Returns a `Vec` of owned snapshots of the objects loaded into the current address space.
+/// Every snapshot pins its module, see `ModuleSnapshot`.
+#[cfg(feature = "alloc")]
+pub fn collect_modules() -> Vec<ModuleSnapshot> {
+    module::snapshot_modules()
+}
 
-    ret
+/// Returns a `Vec` of owned snapshots of the objects loaded into `namespace`, such as a namespace created by `dlmopen`.
+/// Hooking only the modules returned leaves the copies loaded into other namespaces untouched.
+#[cfg(feature = "alloc")]
+pub fn collect_modules_in(namespace: Namespace) -> Vec<ModuleSnapshot> {
+    module::snapshot_namespace(namespace)
 }
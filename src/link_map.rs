@@ -0,0 +1,359 @@
+use super::{
+    elf, iterate_phdr, DynamicSection, DynamicSectionType, LoadedLibrary, Namespace, SegmentType,
+};
+use core::ffi::{c_char, c_int, CStr};
+use core::mem::size_of;
+use core::ops::ControlFlow;
+
+/// The leading fields of `struct link_map`, which glibc, musl and bionic agree on
+#[repr(C)]
+struct LinkMap {
+    l_addr: usize,
+    l_name: *const c_char,
+    l_ld: *const elf::DynEntry,
+    l_next: *const LinkMap,
+}
+
+/// `struct r_debug`, the rendezvous structure the dynamic linker shares with debuggers.
+/// From version 2 onwards it is glibc's `struct r_debug_extended`, which links the `r_debug` of every namespace.
+/// Only ever accessed through raw pointers, as older versions end before `r_next`.
+#[repr(C)]
+struct RDebug {
+    r_version: c_int,
+    r_map: *const LinkMap,
+    _r_brk: usize,
+    _r_state: c_int,
+    _r_ldbase: usize,
+    r_next: *const RDebug,
+}
+
+/// A module found walking the dynamic linker's `link_map` list
+#[derive(Debug, Clone)]
+pub struct LinkMapEntry<'a> {
+    /// The module, as `for_each_module` would report it
+    pub library: LoadedLibrary<'a>,
+    /// The entry's `l_ld`, the address of the module's dynamic section
+    pub dynamic_addr: usize,
+    /// The namespace whose link map the entry was found in
+    pub namespace: Namespace,
+}
+
+/// The main program's program headers, as reported by the kernel through AT_PHDR and AT_PHNUM
+fn main_program_headers() -> Option<&'static [elf::ProgramHeader]> {
+    let phdr = unsafe { libc::getauxval(libc::AT_PHDR) } as *const elf::ProgramHeader;
+    let phnum = unsafe { libc::getauxval(libc::AT_PHNUM) } as usize;
+    match phdr.is_null() || phnum == 0 {
+        true => None,
+        false => Some(unsafe { core::slice::from_raw_parts(phdr, phnum) }),
+    }
+}
+
+/// The main program's dynamic section, rebased by the difference between where its program headers
+/// were mapped and where its PT_PHDR says they should be
+fn main_dynamic(program_headers: &[elf::ProgramHeader]) -> Option<DynamicSection<'static>> {
+    let phdr = program_headers
+        .iter()
+        .find(|p_h| SegmentType::from(p_h.p_type) == SegmentType::Phdr)?;
+    let dynamic = program_headers
+        .iter()
+        .find(|p_h| SegmentType::from(p_h.p_type) == SegmentType::Dynamic)?;
+    let bias = (program_headers.as_ptr() as usize).wrapping_sub(phdr.p_vaddr as usize);
+    let addr = bias.wrapping_add(dynamic.p_vaddr as usize);
+    if addr == 0 {
+        return None;
+    }
+
+    let count = dynamic.p_memsz as usize / size_of::<elf::DynEntry>();
+    Some(DynamicSection {
+        inner: unsafe { core::slice::from_raw_parts(addr as *const elf::DynEntry, count) },
+    })
+}
+
+/// Locate `r_debug` through the main program's DT_DEBUG entry, filled in by the dynamic linker at startup.
+/// Falls back on the dynamic linker's exported `_r_debug` for executables without one.
+fn find_r_debug(main_dynamic: Option<&DynamicSection<'_>>) -> Option<*const RDebug> {
+    let debug = main_dynamic.and_then(|dynamic_section| {
+        dynamic_section
+            .find_section(DynamicSectionType::DT_DEBUG)
+            .map(|entry| entry.d_val_ptr as usize)
+    });
+
+    let r_debug = match debug {
+        Some(addr) if addr != 0 => addr,
+        _ => unsafe { libc::dlsym(libc::RTLD_DEFAULT, c"_r_debug".as_ptr()) as usize },
+    };
+    // A version of 0 means the dynamic linker has not initialized it yet
+    let r_debug = r_debug as *const RDebug;
+    match r_debug.is_null() || unsafe { (*r_debug).r_version } == 0 {
+        true => None,
+        false => Some(r_debug),
+    }
+}
+
+/// Do the program headers describe a PT_DYNAMIC landing on the entry's `l_ld`
+fn describes_entry(program_headers: &[elf::ProgramHeader], entry: &LinkMap) -> bool {
+    program_headers.iter().any(|p_h| {
+        SegmentType::from(p_h.p_type) == SegmentType::Dynamic
+            && entry.l_addr.wrapping_add(p_h.p_vaddr as usize) == entry.l_ld as usize
+    })
+}
+
+/// Read the program headers following the elf header mapped at `base`.
+/// The headers are only trusted if they describe a PT_DYNAMIC landing on the entry's `l_ld`.
+///
+/// # Safety
+/// `base` must be the start of a readable mapping, such as the module base `dladdr` reports.
+unsafe fn program_headers_at(
+    base: usize,
+    entry: &LinkMap,
+) -> Option<&'static [elf::ProgramHeader]> {
+    if base == 0 {
+        return None;
+    }
+
+    let header = unsafe { &*(base as *const elf::FileHeader) };
+    if header.e_ident[..4] != *b"\x7fELF"
+        || header.e_phentsize as usize != size_of::<elf::ProgramHeader>()
+    {
+        return None;
+    }
+
+    let program_headers = unsafe {
+        core::slice::from_raw_parts(
+            (base + header.e_phoff as usize) as *const elf::ProgramHeader,
+            header.e_phnum as usize,
+        )
+    };
+    describes_entry(program_headers, entry).then_some(program_headers)
+}
+
+/// The program headers `dl_iterate_phdr` reports for the module loaded at the entry's load bias.
+/// Only modules of the caller's namespace are reported.
+fn reported_program_headers(entry: &LinkMap) -> Option<&'static [elf::ProgramHeader]> {
+    iterate_phdr(|dl_info, _size| {
+        if dl_info.dlpi_addr as usize != entry.l_addr || dl_info.dlpi_phdr.is_null() {
+            return ControlFlow::Continue(());
+        }
+        let program_headers =
+            unsafe { core::slice::from_raw_parts(dl_info.dlpi_phdr, dl_info.dlpi_phnum as usize) };
+        match describes_entry(program_headers, entry) {
+            true => ControlFlow::Break(program_headers),
+            false => ControlFlow::Continue(()),
+        }
+    })
+}
+
+/// `struct dl_find_object`, as filled by glibc's `_dl_find_object`
+#[cfg(target_env = "gnu")]
+#[repr(C)]
+struct DlFindObject {
+    dlfo_flags: u64,
+    dlfo_map_start: usize,
+    dlfo_map_end: usize,
+    dlfo_link_map: *const LinkMap,
+    dlfo_eh_frame: usize,
+    _reserved: [u64; 7],
+}
+
+/// `_dl_find_object`, which finds the module containing an address in any namespace without taking a lock
+#[cfg(target_env = "gnu")]
+type FindObject = unsafe extern "C" fn(*const libc::c_void, *mut DlFindObject) -> c_int;
+
+/// Look up `_dl_find_object`, available from glibc 2.35.
+/// `dlsym` takes the dynamic linker's lock, so this has to be done before `dl_iterate_phdr` holds it.
+#[cfg(target_env = "gnu")]
+fn find_object_fn() -> Option<FindObject> {
+    let addr = unsafe { libc::dlsym(libc::RTLD_DEFAULT, c"_dl_find_object".as_ptr()) };
+    match addr.is_null() {
+        true => None,
+        false => Some(unsafe { core::mem::transmute::<*mut libc::c_void, FindObject>(addr) }),
+    }
+}
+
+/// The program headers at the start of the mapping `_dl_find_object` reports for the entry's dynamic section
+#[cfg(target_env = "gnu")]
+fn found_program_headers(
+    find_object: FindObject,
+    entry: &LinkMap,
+) -> Option<&'static [elf::ProgramHeader]> {
+    let mut object = core::mem::MaybeUninit::<DlFindObject>::zeroed();
+    if unsafe { find_object(entry.l_ld as *const libc::c_void, object.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    let object = unsafe { object.assume_init() };
+    match core::ptr::eq(object.dlfo_link_map, entry) {
+        true => unsafe { program_headers_at(object.dlfo_map_start, entry) },
+        false => None,
+    }
+}
+
+/// The address the module containing `addr` was mapped at, which is where its elf header lives
+fn mapped_base(addr: usize) -> Option<usize> {
+    let mut info = core::mem::MaybeUninit::<libc::Dl_info>::uninit();
+    match unsafe { libc::dladdr(addr as *const libc::c_void, info.as_mut_ptr()) } {
+        0 => None,
+        _ => Some(unsafe { info.assume_init() }.dli_fbase as usize),
+    }
+}
+
+/// Walk the link map of every namespace, in namespace order.
+/// glibc links the `r_debug` of each namespace to the previous one's as it is created, so a namespace's
+/// position in the chain is its id. Other dynamic linkers only have the base namespace.
+///
+/// # Safety
+/// No library may be loaded or unloaded while the walk is in progress.
+unsafe fn walk_link_maps<B>(
+    main_dynamic: Option<&DynamicSection<'_>>,
+    mut visitor: impl FnMut(Namespace, &LinkMap) -> ControlFlow<B>,
+) -> Option<B> {
+    let mut r_debug = find_r_debug(main_dynamic)?;
+    let mut namespace = Namespace::BASE.id();
+    loop {
+        let mut current = unsafe { (*r_debug).r_map };
+        while let Some(entry) = unsafe { current.as_ref() } {
+            current = entry.l_next;
+            if let ControlFlow::Break(result) = visitor(Namespace::from_id(namespace), entry) {
+                return Some(result);
+            }
+        }
+
+        // Only `r_debug_extended` from version 2 onwards has a next namespace
+        if unsafe { (*r_debug).r_version } < 2 {
+            return None;
+        }
+        r_debug = unsafe { (*r_debug).r_next };
+        if r_debug.is_null() {
+            return None;
+        }
+        namespace += 1;
+    }
+}
+
+/// View a link map entry as a library, with program headers found by `program_headers`
+fn entry_library<'a>(
+    entry: &'a LinkMap,
+    program_headers: Option<&'a [elf::ProgramHeader]>,
+) -> Option<LoadedLibrary<'a>> {
+    let program_headers = program_headers.filter(|headers| !headers.is_empty())?;
+    let name = match entry.l_name.is_null() {
+        true => c"",
+        false => unsafe { CStr::from_ptr(entry.l_name) },
+    };
+    Some(LoadedLibrary {
+        addr: entry.l_addr,
+        name,
+        program_headers,
+    })
+}
+
+/// Visit every object loaded by the dynamic linker by walking the `link_map` lists of `r_debug`,
+/// an alternative to `for_each_module` which does not go through `dl_iterate_phdr`.
+/// `r_debug` is found through the main program's DT_DEBUG entry, or the dynamic linker's `_r_debug` symbol.
+/// On glibc every namespace created by `dlmopen` is walked, the base namespace first.
+///
+/// Each module's program headers are read from the elf header at the start of its mapping, as found by `dladdr`,
+/// or failing that taken from `dl_iterate_phdr` by load bias, and only used once they are verified against the
+/// entry's `l_ld`. Entries whose headers can not be found this way are skipped, nothing is read at an unmapped address.
+///
+/// Returns None if `r_debug` could not be found, or if the visitor never stopped the walk.
+///
+/// # Safety
+/// The list is walked without holding the dynamic linker's lock.
+/// No library may be loaded or unloaded while the walk is in progress.
+pub unsafe fn for_each_link_map_entry<B, F>(mut visitor: F) -> Option<B>
+where
+    F: FnMut(LinkMapEntry<'_>) -> ControlFlow<B>,
+{
+    let main_program_headers = main_program_headers();
+    let main_dynamic = main_program_headers.and_then(main_dynamic);
+    let main_dynamic_addr = main_dynamic
+        .as_ref()
+        .map(|dynamic_section| dynamic_section.entries().as_ptr() as usize);
+
+    unsafe {
+        walk_link_maps(main_dynamic.as_ref(), |namespace, entry| {
+            let dynamic_addr = entry.l_ld as usize;
+            if dynamic_addr == 0 {
+                return ControlFlow::Continue(());
+            }
+
+            let program_headers = match main_dynamic_addr == Some(dynamic_addr) {
+                true => main_program_headers,
+                false => mapped_base(dynamic_addr)
+                    .and_then(|base| program_headers_at(base, entry))
+                    .or_else(|| reported_program_headers(entry)),
+            };
+            match entry_library(entry, program_headers) {
+                Some(library) => visitor(LinkMapEntry {
+                    library,
+                    dynamic_addr,
+                    namespace,
+                }),
+                None => ControlFlow::Continue(()),
+            }
+        })
+    }
+}
+
+/// Visit every object loaded into `namespace` without allocating.
+/// `dl_iterate_phdr` only reports the namespace of its caller, so the namespace's `link_map` list is walked
+/// instead, while `dl_iterate_phdr` holds the dynamic linker's lock.
+/// The visitor may stop the iteration early by returning `ControlFlow::Break`, whose value is returned.
+///
+/// `dladdr` can not be used while the lock is held, so each module's program headers are taken from
+/// `dl_iterate_phdr` by load bias, or on glibc read at the start of the mapping `_dl_find_object` reports,
+/// which other namespaces need. Modules whose headers can not be found either way are skipped.
+pub fn for_each_module_in<B, F>(namespace: Namespace, mut visitor: F) -> Option<B>
+where
+    F: FnMut(LoadedLibrary<'_>) -> ControlFlow<B>,
+{
+    let main_program_headers = main_program_headers();
+    let main_dynamic = main_program_headers.and_then(main_dynamic);
+    let main_dynamic_addr = main_dynamic
+        .as_ref()
+        .map(|dynamic_section| dynamic_section.entries().as_ptr() as usize);
+    #[cfg(target_env = "gnu")]
+    let find_object = find_object_fn();
+
+    iterate_phdr(|_dl_info, _size| {
+        ControlFlow::Break(unsafe {
+            walk_link_maps(main_dynamic.as_ref(), |entry_namespace, entry| {
+                let dynamic_addr = entry.l_ld as usize;
+                if entry_namespace != namespace || dynamic_addr == 0 {
+                    return ControlFlow::Continue(());
+                }
+
+                let program_headers = match main_dynamic_addr == Some(dynamic_addr) {
+                    true => main_program_headers,
+                    false => reported_program_headers(entry),
+                };
+                #[cfg(target_env = "gnu")]
+                let program_headers = program_headers.or_else(|| {
+                    find_object.and_then(|find_object| found_program_headers(find_object, entry))
+                });
+                match entry_library(entry, program_headers) {
+                    Some(library) => visitor(library),
+                    None => ControlFlow::Continue(()),
+                }
+            })
+        })
+    })
+    .flatten()
+}
+
+/// The dynamic section address recorded by the `link_map` entry at `addr`, if it is one of the entries in any namespace.
+/// The entry is only read once it was found walking the lists, so any address can be checked.
+pub(crate) fn link_map_dynamic(addr: usize) -> Option<usize> {
+    let main_dynamic = main_program_headers().and_then(main_dynamic);
+    iterate_phdr(|_dl_info, _size| {
+        ControlFlow::Break(unsafe {
+            walk_link_maps(main_dynamic.as_ref(), |_namespace, entry| {
+                match entry as *const LinkMap as usize == addr {
+                    true => ControlFlow::Break(entry.l_ld as usize),
+                    false => ControlFlow::Continue(()),
+                }
+            })
+        })
+    })
+    .flatten()
+}
@@ -0,0 +1,280 @@
+use super::{
+    elf, for_each_module, for_each_module_in, DynamicError, DynamicLibrary, LoadedLibrary,
+    ModuleKind, Namespace,
+};
+use alloc::borrow::Cow;
+use alloc::ffi::CString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ffi::CStr;
+use core::ops::ControlFlow;
+
+/// A reference on a loaded module, taken through `dlopen(RTLD_NOLOAD)`.
+/// The module can not be unloaded until the reference is released with `dlclose` on drop.
+#[derive(Debug)]
+struct ModulePin {
+    handle: *mut libc::c_void,
+}
+
+// The handle is an opaque token, the dynamic linker synchronizes `dlclose` itself.
+unsafe impl Send for ModulePin {}
+unsafe impl Sync for ModulePin {}
+
+impl ModulePin {
+    /// Take a reference on the already loaded module, never loading it.
+    /// The module is looked up in its own namespace, another namespace may hold a different copy of it.
+    fn acquire(snapshot: &ModuleSnapshot) -> Option<Self> {
+        let path = snapshot.name.as_ptr();
+        let flags = libc::RTLD_NOLOAD | libc::RTLD_LAZY;
+        #[cfg(target_env = "gnu")]
+        let handle = match snapshot.namespace {
+            Some(namespace) => unsafe { libc::dlmopen(namespace.id(), path, flags) },
+            None => unsafe { libc::dlopen(path, flags) },
+        };
+        #[cfg(not(target_env = "gnu"))]
+        let handle = unsafe { libc::dlopen(path, flags) };
+        match handle.is_null() {
+            true => None,
+            false => Some(Self { handle }),
+        }
+    }
+}
+
+impl Drop for ModulePin {
+    fn drop(&mut self) {
+        unsafe { libc::dlclose(self.handle) };
+    }
+}
+
+/// How long the module backing a snapshot is guaranteed to stay mapped
+#[derive(Debug, Clone)]
+enum Residency {
+    /// The main program and the vDSO are never unloaded
+    Permanent,
+    /// Kept loaded by a shared `dlopen` reference, only ever held for its drop
+    Pinned(#[allow(dead_code)] Arc<ModulePin>),
+    /// Could not be pinned, it may be unloaded at any time
+    Unpinned,
+}
+
+/// An owned snapshot of a loaded module.
+/// The name and program headers are copied out of the dynamic linker's bookkeeping, and the module
+/// itself is pinned with a `dlopen(RTLD_NOLOAD)` reference for as long as any clone of the snapshot lives.
+/// Snapshots are `Send + Sync + Clone` and can be handed to worker threads.
+#[derive(Debug, Clone)]
+pub struct ModuleSnapshot {
+    addr: usize,
+    name: CString,
+    program_headers: Vec<elf::ProgramHeader>,
+    kind: ModuleKind,
+    namespace: Option<Namespace>,
+    residency: Residency,
+}
+
+impl ModuleSnapshot {
+    /// Copy everything out of `library`, without pinning it
+    pub(crate) fn copy(library: &LoadedLibrary<'_>) -> Self {
+        Self {
+            addr: library.addr(),
+            name: library.name_cstr().into(),
+            program_headers: library.program_headers.to_vec(),
+            kind: library.kind(),
+            namespace: None,
+            residency: Residency::Unpinned,
+        }
+    }
+
+    /// Access the libraries string name
+    /// This is more the libraries `path` than the name per say
+    /// Paths which are not valid UTF-8 are converted lossily, see `name_cstr` for the raw path.
+    pub fn name(&self) -> Cow<'_, str> {
+        self.name.to_string_lossy()
+    }
+
+    /// Access the libraries raw path, as reported by the dynamic linker
+    pub fn name_cstr(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Access the libraries virtual address
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    /// The role the module plays in the process, classified when the snapshot was taken
+    pub fn kind(&self) -> ModuleKind {
+        self.kind
+    }
+
+    /// The namespace the module was loaded into, if the dynamic linker could tell
+    pub fn namespace(&self) -> Option<Namespace> {
+        self.namespace
+    }
+
+    /// Is the module guaranteed to stay loaded for the lifetime of the snapshot,
+    /// either because it is pinned or because it can never be unloaded
+    pub fn is_pinned(&self) -> bool {
+        !matches!(self.residency, Residency::Unpinned)
+    }
+
+    /// View the snapshot as a library, backed by the copied name and program headers.
+    /// Only available while the module is guaranteed to stay loaded, see `is_pinned`.
+    pub fn library(&self) -> Option<LoadedLibrary<'_>> {
+        match self.is_pinned() {
+            true => Some(unsafe { self.library_unchecked() }),
+            false => None,
+        }
+    }
+
+    /// View the snapshot as a library even if it is not pinned.
+    ///
+    /// # Safety
+    /// The caller must guarantee the module stays loaded while the library, or anything parsed from it, is in use.
+    pub unsafe fn library_unchecked(&self) -> LoadedLibrary<'_> {
+        LoadedLibrary {
+            addr: self.addr,
+            name: &self.name,
+            program_headers: &self.program_headers,
+        }
+    }
+
+    /// Parse the pinned module's dynamic section.
+    /// Fails with `DynamicError::Unpinned` if the module is not guaranteed to stay loaded.
+    pub fn dynamic(&self) -> Result<DynamicLibrary<'_>, DynamicError> {
+        DynamicLibrary::initialize(self.library().ok_or(DynamicError::Unpinned)?)
+    }
+}
+
+impl ModuleSnapshot {
+    /// Look up the facts about the module which need the dynamic linker's lock,
+    /// so can only be gathered once a module iteration released it
+    pub(crate) fn locate(mut self) -> Self {
+        #[cfg(target_env = "gnu")]
+        if self.namespace.is_none() {
+            self.namespace = unsafe { self.library_unchecked() }.namespace();
+        }
+        // Without `dlmopen` there is no other namespace to be loaded into
+        #[cfg(not(target_env = "gnu"))]
+        {
+            self.namespace = Some(Namespace::BASE);
+        }
+        self
+    }
+
+    /// Pin the snapshot's module, unless it can never be unloaded.
+    /// dlopen takes the dynamic linker's lock, so pinning has to wait until a module iteration released it.
+    /// A module unloaded in between fails to pin, `RTLD_NOLOAD` only hands out handles to loaded modules.
+    fn pin(self) -> Self {
+        let mut this = self.locate();
+        this.residency = match this.kind {
+            ModuleKind::MainProgram | ModuleKind::Vdso => Residency::Permanent,
+            ModuleKind::DynamicLinker | ModuleKind::SharedObject => match ModulePin::acquire(&this)
+            {
+                Some(pin) => Residency::Pinned(Arc::new(pin)),
+                None => Residency::Unpinned,
+            },
+        };
+        this
+    }
+
+    /// Is the module still loaded at the same address under the same name
+    pub fn is_loaded(&self) -> bool {
+        let visitor = |library: LoadedLibrary<'_>| match self.is_same_module(&library) {
+            true => ControlFlow::Break(()),
+            false => ControlFlow::Continue(()),
+        };
+        // `dl_iterate_phdr` only sees our own namespace
+        for_each_module(visitor).is_some()
+            || self
+                .namespace
+                .is_some_and(|namespace| for_each_module_in(namespace, visitor).is_some())
+    }
+
+    /// Does `library` describe the same module as the snapshot
+    pub(crate) fn is_same_module(&self, library: &LoadedLibrary<'_>) -> bool {
+        self.addr == library.addr() && self.name.as_c_str() == library.name_cstr()
+    }
+
+    /// Do both snapshots describe the same module
+    pub(crate) fn is_same_snapshot(&self, other: &Self) -> bool {
+        self.addr == other.addr && self.name == other.name
+    }
+
+    /// A pinned copy of the snapshot, if the module is still loaded.
+    /// Snapshots held by a `ModuleRegistry` are not pinned, so they do not keep unloaded modules alive.
+    pub fn pinned(&self) -> Option<Self> {
+        match self.is_pinned() {
+            true => Some(self.clone()),
+            false => Some(self.clone().pin()).filter(Self::is_pinned),
+        }
+    }
+}
+
+/// Copy every module loaded into the current address space, without pinning them
+fn copy_modules() -> Vec<ModuleSnapshot> {
+    let mut snapshots = Vec::new();
+    for_each_module(|library| {
+        snapshots.push(ModuleSnapshot::copy(&library));
+        ControlFlow::<()>::Continue(())
+    });
+    snapshots
+}
+
+/// Snapshot every module loaded into the current address space, pinning each one
+pub(crate) fn snapshot_modules() -> Vec<ModuleSnapshot> {
+    copy_modules()
+        .into_iter()
+        .map(ModuleSnapshot::pin)
+        .collect()
+}
+
+/// Snapshot and pin every module loaded into `namespace`
+pub(crate) fn snapshot_namespace(namespace: Namespace) -> Vec<ModuleSnapshot> {
+    let mut snapshots = Vec::new();
+    for_each_module_in(namespace, |library| {
+        let mut snapshot = ModuleSnapshot::copy(&library);
+        snapshot.namespace = Some(namespace);
+        snapshots.push(snapshot);
+        ControlFlow::<()>::Continue(())
+    });
+    snapshots.into_iter().map(ModuleSnapshot::pin).collect()
+}
+
+/// Snapshot and pin the first module picked by `predicate`
+fn snapshot_first(mut predicate: impl FnMut(&LoadedLibrary<'_>) -> bool) -> Option<ModuleSnapshot> {
+    for_each_module(|library| match predicate(&library) {
+        true => ControlFlow::Break(ModuleSnapshot::copy(&library)),
+        false => ControlFlow::Continue(()),
+    })
+    .map(ModuleSnapshot::pin)
+}
+
+/// Find the executable the process was started with
+pub fn main_executable() -> Option<ModuleSnapshot> {
+    snapshot_first(|library| library.kind() == ModuleKind::MainProgram)
+}
+
+/// Find a module by its full path, or by its file name such as `libc.so.6`
+pub fn find_module_by_name(name: &str) -> Option<ModuleSnapshot> {
+    snapshot_first(|library| {
+        let path = library.name_cstr().to_bytes();
+        let file_name = path.rsplit(|c| *c == b'/').next().unwrap_or(path);
+        path == name.as_bytes() || file_name == name.as_bytes()
+    })
+}
+
+/// Find a module by the DT_SONAME recorded in its dynamic section
+pub fn find_module_by_soname(soname: &str) -> Option<ModuleSnapshot> {
+    snapshot_first(|library| {
+        DynamicLibrary::initialize(library.clone()).is_ok_and(|dyn_lib| {
+            dyn_lib
+                .soname()
+                .is_some_and(|s| s.to_bytes() == soname.as_bytes())
+        })
+    })
+}
+
+/// Find the module with a PT_LOAD segment containing `addr`
+pub fn module_containing(addr: usize) -> Option<ModuleSnapshot> {
+    snapshot_first(|library| library.contains_addr(addr))
+}
@@ -0,0 +1,71 @@
+#[cfg(target_env = "gnu")]
+use super::LoadedLibrary;
+use core::ffi::c_long;
+
+/// `dladdr1` request for the `link_map` of the module containing the address
+#[cfg(target_env = "gnu")]
+const RTLD_DL_LINKMAP: core::ffi::c_int = 2;
+
+/// A dynamic linker namespace, the `Lmid_t` modules loaded through glibc's `dlmopen` are grouped by.
+/// Every namespace holds its own copies of the libraries loaded into it, so the same library may be
+/// loaded several times, once per namespace.
+/// Dynamic linkers without `dlmopen` load everything into the base namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Namespace(c_long);
+
+impl Namespace {
+    /// The namespace the main program and its dependencies are loaded into
+    pub const BASE: Self = Self(0);
+
+    /// Wrap a raw namespace id, such as one reported by `dlinfo(RTLD_DI_LMID)`
+    pub fn from_id(id: c_long) -> Self {
+        Self(id)
+    }
+
+    /// The raw namespace id, as accepted by `dlmopen`
+    pub fn id(&self) -> c_long {
+        self.0
+    }
+
+    /// The namespace of the module containing `addr`.
+    /// This takes the dynamic linker's lock, so it must not be called from within a `for_each_module` visitor.
+    #[cfg(target_env = "gnu")]
+    pub fn containing(addr: usize) -> Option<Self> {
+        let mut info = core::mem::MaybeUninit::<libc::Dl_info>::uninit();
+        let mut link_map = core::ptr::null_mut::<libc::c_void>();
+        let found = unsafe {
+            libc::dladdr1(
+                addr as *const libc::c_void,
+                info.as_mut_ptr(),
+                &mut link_map,
+                RTLD_DL_LINKMAP,
+            )
+        };
+        if found == 0 || link_map.is_null() {
+            return None;
+        }
+
+        // glibc's dlopen handles are the module's link_map
+        let mut id: libc::Lmid_t = 0;
+        match unsafe {
+            libc::dlinfo(
+                link_map,
+                libc::RTLD_DI_LMID,
+                &mut id as *mut libc::Lmid_t as *mut libc::c_void,
+            )
+        } {
+            0 => Some(Self(id)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_env = "gnu")]
+impl LoadedLibrary<'_> {
+    /// The namespace the library was loaded into.
+    /// This takes the dynamic linker's lock, so it must not be called from within a `for_each_module` visitor.
+    pub fn namespace(&self) -> Option<Namespace> {
+        let segment = self.load_headers().next()?;
+        Namespace::containing(segment.absolute_addr(self.addr))
+    }
+}
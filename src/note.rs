@@ -0,0 +1,255 @@
+use super::{checked_slice, LoadedLibrary, Machine, SegmentType};
+use core::mem::size_of;
+
+/// `n_type` of the GNU ABI tag note, under the "GNU" name
+const NT_GNU_ABI_TAG: u32 = 1;
+/// `n_type` of the GNU build id note, under the "GNU" name
+const NT_GNU_BUILD_ID: u32 = 3;
+/// `n_type` of the GNU property note, under the "GNU" name
+const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+/// `n_type` of Android's ABI note, under the "Android" name
+const NT_ANDROID_TYPE_IDENT: u32 = 1;
+
+/// GNU property holding the x86 features every linked object supports
+const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+/// GNU property holding the aarch64 features every linked object supports
+const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc0000000;
+
+/// Size of a note header, `n_namesz`, `n_descsz` and `n_type`, the same for 32 and 64 bit elfs
+const NOTE_HEADER_SIZE: usize = 3 * size_of::<u32>();
+
+/// Read the native endian word at `offset`
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let word = bytes.get(offset..offset.checked_add(size_of::<u32>())?)?;
+    Some(u32::from_ne_bytes(word.try_into().ok()?))
+}
+
+/// Round `value` up to a multiple of the power of two `align`
+fn align_up(value: usize, align: usize) -> Option<usize> {
+    Some(value.checked_add(align - 1)? & !(align - 1))
+}
+
+/// The operating system and minimum kernel version a GNU ABI tag note asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbiTag {
+    /// ELF_NOTE_OS_*, 0 for Linux
+    pub os: u32,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+/// A single entry of a GNU property note
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GnuProperty<'a> {
+    /// GNU_PROPERTY_X86_FEATURE_1_AND, control flow protection every linked object was built with
+    X86Features {
+        /// Indirect branch tracking
+        ibt: bool,
+        /// Shadow stack
+        shstk: bool,
+    },
+    /// GNU_PROPERTY_AARCH64_FEATURE_1_AND, control flow protection every linked object was built with
+    Aarch64Features {
+        /// Branch target identification
+        bti: bool,
+        /// Pointer authentication
+        pac: bool,
+    },
+    /// Any other property, processor specific ones are only decoded for the module's own machine
+    Other { pr_type: u32, data: &'a [u8] },
+}
+
+/// Iterator over the properties of a GNU property note
+#[derive(Debug, Clone)]
+pub struct GnuProperties<'a> {
+    desc: &'a [u8],
+    machine: Option<Machine>,
+}
+
+impl<'a> Iterator for GnuProperties<'a> {
+    type Item = GnuProperty<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pr_type = read_u32(self.desc, 0)?;
+        let data_size = read_u32(self.desc, size_of::<u32>())? as usize;
+        let data_start = 2 * size_of::<u32>();
+        let data = data_start
+            .checked_add(data_size)
+            .and_then(|data_end| self.desc.get(data_start..data_end));
+        let Some(data) = data else {
+            self.desc = &[];
+            return None;
+        };
+        // Each property is padded to the elf class' word size
+        let next = align_up(data_start + data_size, size_of::<usize>()).unwrap_or(usize::MAX);
+        self.desc = self.desc.get(next..).unwrap_or_default();
+
+        let features = read_u32(data, 0).filter(|_| data.len() == size_of::<u32>());
+        Some(match (self.machine, pr_type, features) {
+            (
+                Some(Machine::X86 | Machine::X86_64),
+                GNU_PROPERTY_X86_FEATURE_1_AND,
+                Some(features),
+            ) => GnuProperty::X86Features {
+                ibt: features & 1 != 0,
+                shstk: features & 2 != 0,
+            },
+            (Some(Machine::AArch64), GNU_PROPERTY_AARCH64_FEATURE_1_AND, Some(features)) => {
+                GnuProperty::Aarch64Features {
+                    bti: features & 1 != 0,
+                    pac: features & 2 != 0,
+                }
+            }
+            _ => GnuProperty::Other { pr_type, data },
+        })
+    }
+}
+
+/// The decoded contents of a note
+#[derive(Debug, Clone)]
+pub enum NoteKind<'a> {
+    /// NT_GNU_BUILD_ID, the identifier the linker derived from the object's contents
+    GnuBuildId(&'a [u8]),
+    /// NT_GNU_ABI_TAG
+    GnuAbiTag(AbiTag),
+    /// NT_GNU_PROPERTY_TYPE_0
+    GnuProperties(GnuProperties<'a>),
+    /// Android's NT_ANDROID_TYPE_IDENT, holding the API level the object was built for
+    AndroidIdent { api_level: u32 },
+    /// Any other note, see `Note::name`, `Note::note_type` and `Note::desc`
+    Other,
+}
+
+/// A single note of a PT_NOTE segment
+#[derive(Debug, Clone)]
+pub struct Note<'a> {
+    name: &'a [u8],
+    note_type: u32,
+    desc: &'a [u8],
+    machine: Option<Machine>,
+}
+
+impl<'a> Note<'a> {
+    /// The note's owner, such as `GNU`, without its null terminator
+    pub fn name(&self) -> &'a [u8] {
+        self.name
+    }
+
+    /// The note's raw `n_type`, whose meaning depends on its name
+    pub fn note_type(&self) -> u32 {
+        self.note_type
+    }
+
+    /// The note's raw descriptor
+    pub fn desc(&self) -> &'a [u8] {
+        self.desc
+    }
+
+    /// Decode the notes this crate knows of
+    pub fn kind(&self) -> NoteKind<'a> {
+        match (self.name, self.note_type) {
+            (b"GNU", NT_GNU_BUILD_ID) => NoteKind::GnuBuildId(self.desc),
+            (b"GNU", NT_GNU_PROPERTY_TYPE_0) => NoteKind::GnuProperties(GnuProperties {
+                desc: self.desc,
+                machine: self.machine,
+            }),
+            (b"GNU", NT_GNU_ABI_TAG) => {
+                let word = |index| read_u32(self.desc, index * size_of::<u32>());
+                match (word(0), word(1), word(2), word(3)) {
+                    (Some(os), Some(major), Some(minor), Some(patch)) => {
+                        NoteKind::GnuAbiTag(AbiTag {
+                            os,
+                            major,
+                            minor,
+                            patch,
+                        })
+                    }
+                    _ => NoteKind::Other,
+                }
+            }
+            (b"Android", NT_ANDROID_TYPE_IDENT) => match read_u32(self.desc, 0) {
+                Some(api_level) => NoteKind::AndroidIdent { api_level },
+                None => NoteKind::Other,
+            },
+            _ => NoteKind::Other,
+        }
+    }
+}
+
+/// Iterator over the notes of a single PT_NOTE segment
+struct SegmentNotes<'a> {
+    notes: &'a [u8],
+    align: usize,
+    machine: Option<Machine>,
+}
+
+impl<'a> Iterator for SegmentNotes<'a> {
+    type Item = Note<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let name_size = read_u32(self.notes, 0)? as usize;
+        let desc_size = read_u32(self.notes, size_of::<u32>())? as usize;
+        let note_type = read_u32(self.notes, 2 * size_of::<u32>())?;
+
+        let name_end = NOTE_HEADER_SIZE.checked_add(name_size);
+        let desc_start = name_end.and_then(|name_end| align_up(name_end, self.align));
+        let desc_end = desc_start.and_then(|desc_start| desc_start.checked_add(desc_size));
+        let (Some(name_end), Some(desc_start), Some(desc_end)) = (name_end, desc_start, desc_end)
+        else {
+            self.notes = &[];
+            return None;
+        };
+        let (Some(name), Some(desc)) = (
+            self.notes.get(NOTE_HEADER_SIZE..name_end),
+            self.notes.get(desc_start..desc_end),
+        ) else {
+            // A truncated note ends the segment
+            self.notes = &[];
+            return None;
+        };
+
+        let next = align_up(desc_end, self.align).unwrap_or(usize::MAX);
+        self.notes = self.notes.get(next..).unwrap_or_default();
+        Some(Note {
+            name: name.strip_suffix(b"\0").unwrap_or(name),
+            note_type,
+            desc,
+            machine: self.machine,
+        })
+    }
+}
+
+impl LoadedLibrary<'_> {
+    /// Iterate the notes of every PT_NOTE segment.
+    /// Segments lying outside of the library's PT_LOAD segments, or with an alignment other than 4 or 8, are skipped.
+    pub fn notes(&self) -> impl Iterator<Item = Note<'_>> {
+        let machine = self.elf_header().map(|header| header.machine());
+        self.program_headers()
+            .filter(|p_h| p_h.segment_type() == SegmentType::Note)
+            .filter_map(move |p_h| {
+                // Notes are 4 byte aligned, apart from GNU property notes which take the 64 bit elf's 8
+                let align = match p_h.alignment() {
+                    0..=4 => 4,
+                    8 => 8,
+                    _ => return None,
+                };
+                let notes =
+                    checked_slice::<u8>(self, p_h.absolute_addr(self.addr), p_h.file_size())?;
+                Some(SegmentNotes {
+                    notes,
+                    align,
+                    machine,
+                })
+            })
+            .flatten()
+    }
+
+    /// The GNU build id the linker recorded in the library, if it was linked with one
+    pub fn build_id(&self) -> Option<&[u8]> {
+        self.notes().find_map(|note| match note.kind() {
+            NoteKind::GnuBuildId(build_id) => Some(build_id),
+            _ => None,
+        })
+    }
+}
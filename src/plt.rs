@@ -0,0 +1,327 @@
+use super::{
+    arch, checked_slice, DynamicLibrary, Relocation, RelocationKind, RelocationSource,
+    RelocationTable,
+};
+use core::ops::Range;
+
+/// Read the little endian word at `offset`
+fn read_u32(code: &[u8], offset: usize) -> Option<u32> {
+    let word = code.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(word.try_into().ok()?))
+}
+
+/// x86_64 `.plt` and `.plt.sec` entries are 16 byte aligned, optionally start with `endbr64`
+/// and jump through their slot with a rip relative `jmp *disp32(%rip)`, which may carry a `bnd` prefix.
+#[cfg(target_arch = "x86_64")]
+mod stub {
+    pub const ALIGN: usize = 16;
+    /// PLT0, then a `.plt` and a `.plt.sec` entry per JUMP_SLOT
+    pub const HEADER: usize = 16;
+    pub const ENTRY_SPAN: usize = 32;
+    const ENDBR64: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfa];
+
+    /// The slot the stub at `code[offset..]`, mapped at `addr`, jumps through
+    pub fn decode(code: &[u8], offset: usize, addr: usize, _got: usize) -> Option<(usize, usize)> {
+        let mut at = offset;
+        if code.get(at..at + 4) == Some(&ENDBR64) {
+            at += 4;
+        }
+        if code.get(at) == Some(&0xf2) {
+            at += 1;
+        }
+        if code.get(at..at + 2) != Some(&[0xff, 0x25]) {
+            return None;
+        }
+        let disp = super::read_u32(code, at + 2)? as i32 as isize;
+        let next = addr + (at + 6 - offset);
+        Some((addr, next.wrapping_add_signed(disp)))
+    }
+}
+
+/// i386 PLT entries are 16 byte aligned, optionally start with `endbr32` and jump through their slot
+/// with `jmp *disp32(%ebx)` relative to the GOT in position independent code, `jmp *abs32` otherwise.
+#[cfg(target_arch = "x86")]
+mod stub {
+    pub const ALIGN: usize = 16;
+    /// PLT0, then a `.plt` and a `.plt.sec` entry per JUMP_SLOT
+    pub const HEADER: usize = 16;
+    pub const ENTRY_SPAN: usize = 32;
+    const ENDBR32: [u8; 4] = [0xf3, 0x0f, 0x1e, 0xfb];
+
+    /// The slot the stub at `code[offset..]`, mapped at `addr`, jumps through
+    pub fn decode(code: &[u8], offset: usize, addr: usize, got: usize) -> Option<(usize, usize)> {
+        let mut at = offset;
+        if code.get(at..at + 4) == Some(&ENDBR32) {
+            at += 4;
+        }
+        if code.get(at) == Some(&0xf2) {
+            at += 1;
+        }
+        let operand = super::read_u32(code, at + 2)? as usize;
+        match code.get(at..at + 2)? {
+            [0xff, 0xa3] => Some((addr, got.wrapping_add(operand))),
+            [0xff, 0x25] => Some((addr, operand)),
+            _ => None,
+        }
+    }
+}
+
+/// aarch64 PLT entries load their slot with `adrp x16, slot` / `ldr x17, [x16, #lo]` / `add x16, x16, #lo`,
+/// preceded by `bti c` when built for branch target identification.
+#[cfg(target_arch = "aarch64")]
+mod stub {
+    pub const ALIGN: usize = 4;
+    /// PLT0, then an entry per JUMP_SLOT, both padded for BTI
+    pub const HEADER: usize = 32;
+    pub const ENTRY_SPAN: usize = 16;
+    const BTI_C: u32 = 0xd503245f;
+
+    /// The slot the stub at `code[offset..]`, mapped at `addr`, jumps through
+    pub fn decode(code: &[u8], offset: usize, addr: usize, _got: usize) -> Option<(usize, usize)> {
+        let adrp = super::read_u32(code, offset)?;
+        let ldr = super::read_u32(code, offset + 4)?;
+        let add = super::read_u32(code, offset + 8)?;
+        if adrp & 0x9f00_001f != 0x9000_0010
+            || ldr & 0xffc0_03ff != 0xf940_0211
+            || add & 0xffc0_03ff != 0x9100_0210
+        {
+            return None;
+        }
+
+        // 21 bit signed page count, split into immhi and immlo
+        let pages = (((adrp >> 5) & 0x7ffff) << 2 | (adrp >> 29) & 3) as i32;
+        let pages = (pages << 11 >> 11) as isize;
+        let page = (addr & !0xfff).wrapping_add_signed(pages << 12);
+        let slot = page + ((ldr >> 10) & 0xfff) as usize * 8;
+
+        let start = match offset
+            .checked_sub(4)
+            .and_then(|previous| super::read_u32(code, previous))
+        {
+            Some(BTI_C) => addr - 4,
+            _ => addr,
+        };
+        Some((start, slot))
+    }
+}
+
+/// arm PLT entries compute their slot with `add ip, pc, #imm` / `add ip, ip, #imm` / `ldr pc, [ip, #imm]!`,
+/// preceded by `bx pc` / `nop` when entered from thumb code.
+#[cfg(target_arch = "arm")]
+mod stub {
+    pub const ALIGN: usize = 4;
+    /// PLT0, then an entry per JUMP_SLOT, the long form or the thumb prefixed short one
+    pub const HEADER: usize = 32;
+    pub const ENTRY_SPAN: usize = 16;
+    const THUMB_BX_PC: u32 = 0x46c0_4778;
+
+    /// An ARM modified immediate, 8 bits rotated right by twice the 4 bit rotation
+    fn immediate(instruction: u32) -> usize {
+        (instruction & 0xff).rotate_right(((instruction >> 8) & 0xf) * 2) as usize
+    }
+
+    /// The slot the stub at `code[offset..]`, mapped at `addr`, jumps through
+    pub fn decode(code: &[u8], offset: usize, addr: usize, _got: usize) -> Option<(usize, usize)> {
+        let first = super::read_u32(code, offset)?;
+        let second = super::read_u32(code, offset + 4)?;
+        let load = super::read_u32(code, offset + 8)?;
+        if first & 0xffff_f000 != 0xe28f_c000
+            || second & 0xffff_f000 != 0xe28c_c000
+            || load & 0xffff_f000 != 0xe5bc_f000
+        {
+            return None;
+        }
+
+        // pc reads 8 bytes ahead of the executing instruction
+        let slot = (addr + 8)
+            .wrapping_add(immediate(first))
+            .wrapping_add(immediate(second))
+            .wrapping_add((load & 0xfff) as usize);
+        let start = match offset
+            .checked_sub(4)
+            .and_then(|previous| super::read_u32(code, previous))
+        {
+            Some(THUMB_BX_PC) => addr - 4,
+            _ => addr,
+        };
+        Some((start, slot))
+    }
+}
+
+/// PLT stubs of other architectures are not decoded
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "x86",
+    target_arch = "aarch64",
+    target_arch = "arm"
+)))]
+mod stub {
+    pub const ALIGN: usize = 4;
+    pub const HEADER: usize = 32;
+    pub const ENTRY_SPAN: usize = 16;
+
+    pub fn decode(
+        _code: &[u8],
+        _offset: usize,
+        _addr: usize,
+        _got: usize,
+    ) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+/// A PLT stub, the code a call to an imported function lands on before jumping through its slot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PltStub {
+    /// The JUMP_SLOT relocation of the slot the stub jumps through
+    pub relocation: Relocation,
+    /// Absolute address of the stub's first instruction, such as its `endbr64` or `bti c`
+    pub addr: usize,
+}
+
+impl DynamicLibrary<'_> {
+    /// The plt relocation at `index`
+    fn plt_relocation(&self, index: usize) -> Option<Relocation> {
+        match self.plt()? {
+            RelocationTable::WithoutAddend(relocs) => relocs
+                .read_at(index)
+                .map(|rel| Relocation::from_rel(rel, RelocationSource::Plt)),
+            RelocationTable::WithAddend(relocs) => relocs
+                .read_at(index)
+                .map(|rela| Relocation::from_rela(rela, RelocationSource::Plt)),
+        }
+    }
+
+    /// The JUMP_SLOT relocation applying to the slot at `slot`.
+    /// Slots usually follow the reserved GOT entries in relocation order, which is tried first, otherwise the plt
+    /// relocations are searched by offset, which linkers emit in ascending order.
+    fn jump_slot_at(&self, slot: usize) -> Option<Relocation> {
+        let is_slot = |relocation: &Relocation| {
+            relocation.kind() == RelocationKind::JumpSlot
+                && self.relocation_addr(relocation) == slot
+        };
+        let reserved = arch::reserved_got_indices().map_or(0, |indices| indices.len());
+        let guess = self
+            .got_addr()
+            .and_then(|got| slot.checked_sub(got))
+            .map(|offset| offset / size_of::<usize>())
+            .and_then(|index| index.checked_sub(reserved))
+            .and_then(|index| self.plt_relocation(index))
+            .filter(is_slot);
+        guess.or_else(|| {
+            let offset = slot.wrapping_sub(self.base_addr());
+            let index = match self.plt()? {
+                RelocationTable::WithoutAddend(relocs) => relocs
+                    .entries()
+                    .partition_point(|rel| (rel.r_offset as usize) < offset),
+                RelocationTable::WithAddend(relocs) => relocs
+                    .entries()
+                    .partition_point(|rela| (rela.r_offset as usize) < offset),
+            };
+            self.plt_relocation(index).filter(is_slot)
+        })
+    }
+
+    /// The readable executable segments of the library, as their start address and code.
+    /// Execute only segments can not be read without faulting and are skipped.
+    fn code_segments(&self) -> impl Iterator<Item = (usize, &[u8])> + '_ {
+        let library = self.library();
+        library
+            .load_headers()
+            .filter(|p_h| p_h.is_executable() && p_h.is_readable())
+            .filter_map(move |p_h| {
+                let start = p_h.absolute_addr(library.addr());
+                let code = checked_slice::<u8>(library, start, p_h.file_size())?;
+                Some((start, code))
+            })
+    }
+
+    /// Decode the stubs starting within `window` of `code` mapped at `start`, yielding each stub's address and the
+    /// slot it jumps through
+    fn decode_stubs<'c>(
+        &self,
+        start: usize,
+        code: &'c [u8],
+        window: Range<usize>,
+    ) -> impl Iterator<Item = (usize, usize)> + 'c {
+        let got = self.got_addr().unwrap_or_default();
+        let first = window.start.max(start).next_multiple_of(stub::ALIGN) - start;
+        let last = window.end.min(start + code.len()).saturating_sub(start);
+        (first..last)
+            .step_by(stub::ALIGN)
+            .filter_map(move |offset| stub::decode(code, offset, start + offset, got))
+    }
+
+    /// The code segment holding the library's PLT, along with the addresses its stubs lie within.
+    /// A slot still waiting on lazy binding points into the PLT, so its first stub is searched for among the entries
+    /// of every JUMP_SLOT on either side of it. Once every slot is bound the first stub is searched for from the
+    /// start of the code instead. The stubs then span at most the entries of every JUMP_SLOT.
+    fn plt_window(&self) -> Option<(usize, &[u8], Range<usize>)> {
+        let count = match self.plt()? {
+            RelocationTable::WithoutAddend(relocs) => relocs.entries().len(),
+            RelocationTable::WithAddend(relocs) => relocs.entries().len(),
+        };
+        let span = count
+            .saturating_mul(stub::ENTRY_SPAN)
+            .saturating_add(stub::HEADER);
+        let first_stub = |start: usize, code: &[u8], search: Range<usize>| {
+            self.decode_stubs(start, code, search)
+                .find(|&(_, slot)| self.jump_slot_at(slot).is_some())
+                .map(|(first, _)| first)
+        };
+
+        let lazy = (0..count)
+            .filter_map(|index| self.plt_relocation(index))
+            .find(|relocation| self.is_slot_unresolved(relocation))
+            .and_then(|relocation| self.slot_value(&relocation));
+        let near_lazy = lazy.and_then(|anchor| {
+            let search = anchor.saturating_sub(span)..anchor.saturating_add(span);
+            self.code_segments()
+                .filter(|&(start, code)| (start..start + code.len()).contains(&anchor))
+                .find_map(|(start, code)| {
+                    Some((start, code, first_stub(start, code, search.clone())?))
+                })
+        });
+        let (start, code, first) = near_lazy.or_else(|| {
+            self.code_segments().find_map(|(start, code)| {
+                Some((
+                    start,
+                    code,
+                    first_stub(start, code, start..start + code.len())?,
+                ))
+            })
+        })?;
+        Some((start, code, first..first.saturating_add(span)))
+    }
+
+    /// Decode the stubs of the library's PLT, yielding each stub's address and the slot it jumps through
+    fn decoded_stubs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.plt_window()
+            .into_iter()
+            .flat_map(|(start, code, window)| self.decode_stubs(start, code, window))
+    }
+
+    /// Iterate the PLT stubs of every JUMP_SLOT relocation, found by decoding the library's PLT.
+    /// x86_64 and i386 `.plt` and IBT `.plt.sec` entries, aarch64 entries with or without BTI, and arm entries are decoded.
+    /// Imports called through a GLOB_DAT slot, such as `.plt.got` entries, have no JUMP_SLOT and are not reported.
+    pub fn plt_stubs(&self) -> impl Iterator<Item = PltStub> + '_ {
+        self.decoded_stubs().filter_map(|(addr, slot)| {
+            Some(PltStub {
+                relocation: self.jump_slot_at(slot)?,
+                addr,
+            })
+        })
+    }
+
+    /// Address of the PLT stub jumping through the slot of the JUMP_SLOT `relocation`.
+    /// The stubs are decoded until one jumps through the slot, without resolving the relocation of every other stub.
+    pub fn plt_stub_addr(&self, relocation: &Relocation) -> Option<usize> {
+        if relocation.kind() != RelocationKind::JumpSlot {
+            return None;
+        }
+        let target = self.relocation_addr(relocation);
+        self.decoded_stubs()
+            .find(|&(_, slot)| slot == target)
+            .map(|(addr, _)| addr)
+    }
+}
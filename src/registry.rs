@@ -0,0 +1,232 @@
+use super::{
+    for_each_module, link_map_counters, DynamicError, DynamicLibrary, DynamicTables, LoadedLibrary,
+    ModuleSnapshot,
+};
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+/// Whether a module appeared in or disappeared from the link map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleChange {
+    Added,
+    Removed,
+}
+
+/// A module observed being loaded or unloaded by a `ModuleRegistry`
+#[derive(Debug, Clone)]
+pub struct ModuleEvent {
+    /// The registry generation the change was first observed at
+    pub generation: u64,
+    pub change: ModuleChange,
+    pub module: ModuleSnapshot,
+}
+
+/// A cached list of the loaded modules, only rebuilt when the link map changes.
+/// Changes are detected through the dynamic linker's `dlpi_adds` / `dlpi_subs` counters, which makes an
+/// unchanged refresh a single `dl_iterate_phdr` callback. On dynamic linkers without the counters every refresh
+/// enumerates the modules and compares them against the cache.
+/// Like `for_each_module` only the namespace of the caller is tracked.
+///
+/// Cached snapshots are not pinned, so the registry never keeps an unloaded module alive.
+/// Each module's dynamic section is parsed once when it is first seen, use `ModuleSnapshot::pinned` and
+/// `ModuleRegistry::dynamic` to view a cached module through the parsed tables.
+///
+/// The history of changes is kept until discarded with `discard_changes_through`.
+#[derive(Debug, Clone)]
+pub struct ModuleRegistry {
+    generation: u64,
+    /// The dynamic linker's load and unload counters as of the last rebuild
+    counters: Option<(u64, u64)>,
+    modules: Vec<ModuleSnapshot>,
+    /// The tables parsed for each cached module, None if its dynamic section could not be parsed.
+    /// They point into the module's memory, and are only attached to pinned snapshots.
+    tables: Vec<Option<DynamicTables<'static>>>,
+    events: Vec<ModuleEvent>,
+}
+
+/// A module found rebuilding the registry
+struct Seen {
+    /// Index of the cached module it was kept as
+    cached: Option<usize>,
+    /// A fresh snapshot and parse, taken for new modules and for every module once some were unloaded
+    fresh: Option<(ModuleSnapshot, Option<DynamicTables<'static>>)>,
+}
+
+impl Default for ModuleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleRegistry {
+    /// Create a registry populated with the currently loaded modules, all reported as added at generation 1
+    pub fn new() -> Self {
+        let mut registry = Self {
+            generation: 0,
+            counters: None,
+            modules: Vec::new(),
+            tables: Vec::new(),
+            events: Vec::new(),
+        };
+        registry.rebuild();
+        registry
+    }
+
+    /// The registry's generation, bumped every time a refresh observes the set of modules changing
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The cached modules, in link map order
+    pub fn modules(&self) -> &[ModuleSnapshot] {
+        &self.modules
+    }
+
+    /// Bring the cache up to date with the link map.
+    /// Returns whether any module was added or removed.
+    pub fn refresh(&mut self) -> bool {
+        let current = link_map_counters();
+        if current.is_some() && current == self.counters {
+            return false;
+        }
+        self.rebuild()
+    }
+
+    /// Parse the dynamic section of the pinned `module`, reusing the tables parsed when the registry first saw it.
+    /// Modules the registry does not hold or could not parse are parsed anew, as is every module once some
+    /// module was unloaded after the last refresh, since it may have been loaded again at the same address.
+    /// Fails with `DynamicError::Unpinned` if the module is not guaranteed to stay loaded.
+    pub fn dynamic<'m>(
+        &self,
+        module: &'m ModuleSnapshot,
+    ) -> Result<DynamicLibrary<'m>, DynamicError> {
+        let library = module.library().ok_or(DynamicError::Unpinned)?;
+        let unloaded = match (self.counters, link_map_counters()) {
+            (Some((_, before)), Some((_, now))) => now != before,
+            _ => false,
+        };
+        let tables = self
+            .modules
+            .iter()
+            .position(|cached| !unloaded && cached.is_same_snapshot(module))
+            .and_then(|index| self.tables[index]);
+        match tables {
+            Some(tables) => Ok(DynamicLibrary::from_tables(library, tables)),
+            None => DynamicLibrary::initialize(library),
+        }
+    }
+
+    /// Every module added or removed after `generation`, oldest first.
+    /// Passing 0 replays the history kept, which starts with the initially loaded modules.
+    pub fn changes_since(&self, generation: u64) -> impl Iterator<Item = &ModuleEvent> {
+        let start = self
+            .events
+            .partition_point(|event| event.generation <= generation);
+        self.events[start..].iter()
+    }
+
+    /// Drop the changes observed up to and including `generation`, once every reader has seen them.
+    /// `changes_since` no longer reports them, and the snapshots of removed modules are released.
+    pub fn discard_changes_through(&mut self, generation: u64) {
+        let end = self
+            .events
+            .partition_point(|event| event.generation <= generation);
+        self.events.drain(..end);
+    }
+
+    /// Enumerate the modules and record the difference to the cache.
+    ///
+    /// The dynamic linker appends every module it loads to the link map, so the modules kept since the last
+    /// rebuild come first in their previous order, followed by those loaded since. A cached module found out of
+    /// that order was unloaded and loaded again. One reloaded at the end of the kept modules is only told apart
+    /// by the load counter: when modules were unloaded and more were loaded than the link map gained, that many
+    /// trailing kept modules are taken as reloaded, which may report a module that stayed loaded as replaced.
+    fn rebuild(&mut self) -> bool {
+        // Sample the counters first, a change racing the enumeration is then picked up by the next refresh
+        let counters = link_map_counters();
+        let (loads, unloaded) = match (self.counters, counters) {
+            (Some((adds, subs)), Some((now_adds, now_subs))) => {
+                (now_adds.wrapping_sub(adds) as usize, now_subs != subs)
+            }
+            _ => (0, false),
+        };
+        self.counters = counters;
+
+        let parse = |library: LoadedLibrary<'_>| {
+            DynamicLibrary::initialize(library)
+                .ok()
+                .map(|dyn_lib| unsafe {
+                    core::mem::transmute::<DynamicTables<'_>, DynamicTables<'static>>(
+                        dyn_lib.into_tables(),
+                    )
+                })
+        };
+        let mut seen = Vec::<Seen>::new();
+        let mut last_kept = None;
+        let mut in_order = true;
+        for_each_module(|library| {
+            let cached = self
+                .modules
+                .iter()
+                .position(|m| m.is_same_module(&library))
+                .filter(|&index| in_order && last_kept.is_none_or(|last| index > last));
+            last_kept = cached.or(last_kept);
+            in_order &= cached.is_some();
+            // The module can not be unloaded while `dl_iterate_phdr` holds the lock, so it is parsed now.
+            // Kept modules are parsed again in case they turn out to have been reloaded.
+            let fresh = (cached.is_none() || unloaded)
+                .then(|| (ModuleSnapshot::copy(&library), parse(library)));
+            seen.push(Seen { cached, fresh });
+            ControlFlow::<()>::Continue(())
+        });
+
+        let kept = seen.iter().filter(|module| module.cached.is_some()).count();
+        if unloaded {
+            let reloaded = loads.saturating_sub(seen.len() - kept).min(kept);
+            for module in &mut seen[kept - reloaded..kept] {
+                module.cached = None;
+            }
+        }
+
+        let generation = self.generation + 1;
+        let event = |change, module: &ModuleSnapshot| ModuleEvent {
+            generation,
+            change,
+            module: module.clone(),
+        };
+        let mut events = self
+            .modules
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !seen.iter().any(|module| module.cached == Some(*index)))
+            .map(|(_, module)| event(ModuleChange::Removed, module))
+            .collect::<Vec<_>>();
+
+        let mut current = Vec::with_capacity(seen.len());
+        let mut tables = Vec::with_capacity(seen.len());
+        for module in seen {
+            match (module.cached, module.fresh) {
+                (Some(index), _) => {
+                    current.push(self.modules[index].clone());
+                    tables.push(self.tables[index]);
+                }
+                (None, Some((snapshot, parsed))) => {
+                    let snapshot = snapshot.locate();
+                    events.push(event(ModuleChange::Added, &snapshot));
+                    current.push(snapshot);
+                    tables.push(parsed);
+                }
+                (None, None) => unreachable!("modules which are not kept are always copied"),
+            }
+        }
+
+        self.modules = current;
+        self.tables = tables;
+        if events.is_empty() {
+            return false;
+        }
+        self.generation = generation;
+        self.events.extend(events);
+        true
+    }
+}
@@ -0,0 +1,173 @@
+use super::{DynamicLibrary, DynamicSectionType, ElfType, GnuProperty, NoteKind, SegmentType};
+use core::ffi::CStr;
+
+/// DT_FLAGS: the object contains text relocations
+const DF_TEXTREL: usize = 0x4;
+/// DT_FLAGS: relocations are processed at load time
+const DF_BIND_NOW: usize = 0x8;
+/// DT_FLAGS_1: relocations are processed at load time
+const DF_1_NOW: usize = 0x1;
+/// DT_FLAGS_1: the object is a position independent executable
+const DF_1_PIE: usize = 0x0800_0000;
+
+/// How much of the module is made read only once relocations are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relro {
+    /// No PT_GNU_RELRO segment
+    None,
+    /// PT_GNU_RELRO without immediate binding, the PLT GOT stays writable for lazy binding
+    Partial,
+    /// PT_GNU_RELRO with immediate binding, the whole GOT is read only
+    Full,
+}
+
+/// Control flow protection the module was built with, as recorded in its GNU property note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ControlFlowProtection {
+    /// x86 indirect branch tracking
+    pub ibt: bool,
+    /// x86 shadow stack
+    pub shstk: bool,
+    /// aarch64 branch target identification
+    pub bti: bool,
+    /// aarch64 pointer authentication
+    pub pac: bool,
+}
+
+/// The hardening a module was built with, in the spirit of `checksec`
+#[derive(Debug, Clone)]
+pub struct SecurityReport<'a> {
+    /// Read only relocations
+    pub relro: Relro,
+    /// Loaded at an address of the dynamic linker's choosing, always the case for shared objects
+    pub position_independent: bool,
+    /// A PT_GNU_STACK segment asks for a non executable stack
+    pub nx_stack: bool,
+    /// Relocations apply to read only segments, which have to be made writable to process them
+    pub text_relocations: bool,
+    /// Imports `__stack_chk_fail`, or `__stack_chk_guard` as bionic's stack protector does
+    pub stack_protector: bool,
+    /// Number of imported FORTIFY_SOURCE checked functions, such as `__memcpy_chk`
+    pub fortified_imports: usize,
+    /// The DT_RPATH search path, deprecated in favour of DT_RUNPATH
+    pub rpath: Option<&'a CStr>,
+    /// The DT_RUNPATH search path
+    pub runpath: Option<&'a CStr>,
+    /// Control flow protection from the GNU property note
+    pub control_flow: ControlFlowProtection,
+}
+
+impl DynamicLibrary<'_> {
+    /// The value of a flags entry, 0 if the library does not have it
+    fn dynamic_flags(&self, tag: DynamicSectionType) -> usize {
+        self.dyn_section()
+            .find_section(tag)
+            .map_or(0, |entry| entry.d_val_ptr as usize)
+    }
+
+    /// The string a DT_RPATH or DT_RUNPATH entry points at
+    fn search_path(&self, tag: DynamicSectionType) -> Option<&CStr> {
+        let entry = self.dyn_section().find_section(tag)?;
+        self.string_table().read_cstr_at(entry.d_val_ptr as usize)
+    }
+
+    /// Report the hardening the library was built with, from its program headers, dynamic section, imports and notes
+    pub fn security_report(&self) -> SecurityReport<'_> {
+        let library = self.library();
+        let flags = self.dynamic_flags(DynamicSectionType::DT_FLAGS);
+        let flags_1 = self.dynamic_flags(DynamicSectionType::DT_FLAGS_1);
+
+        let has_relro = library
+            .program_headers()
+            .any(|p_h| p_h.segment_type() == SegmentType::GnuRelro);
+        let bind_now = flags & DF_BIND_NOW != 0
+            || flags_1 & DF_1_NOW != 0
+            || self
+                .dyn_section()
+                .find_section(DynamicSectionType::DT_BIND_NOW)
+                .is_some();
+        let relro = match (has_relro, bind_now) {
+            (false, _) => Relro::None,
+            (true, false) => Relro::Partial,
+            (true, true) => Relro::Full,
+        };
+
+        let position_independent = flags_1 & DF_1_PIE != 0
+            || match library.elf_header() {
+                Some(header) => header.file_type() == ElfType::SharedObject,
+                None => library.addr() != 0,
+            };
+
+        let nx_stack = library
+            .program_headers()
+            .find(|p_h| p_h.segment_type() == SegmentType::GnuStack)
+            .is_some_and(|p_h| !p_h.is_executable());
+
+        let text_relocations = flags & DF_TEXTREL != 0
+            || self
+                .dyn_section()
+                .find_section(DynamicSectionType::DT_TEXTREL)
+                .is_some();
+
+        let mut stack_protector = false;
+        let mut fortified_imports = 0;
+        for name in self.imports() {
+            match name {
+                b"__stack_chk_fail" | b"__stack_chk_guard" => stack_protector = true,
+                name if name.starts_with(b"__") && name.ends_with(b"_chk") => {
+                    fortified_imports += 1
+                }
+                _ => {}
+            }
+        }
+
+        let mut control_flow = ControlFlowProtection::default();
+        for note in library.notes() {
+            let NoteKind::GnuProperties(properties) = note.kind() else {
+                continue;
+            };
+            for property in properties {
+                match property {
+                    GnuProperty::X86Features { ibt, shstk } => {
+                        control_flow.ibt |= ibt;
+                        control_flow.shstk |= shstk;
+                    }
+                    GnuProperty::Aarch64Features { bti, pac } => {
+                        control_flow.bti |= bti;
+                        control_flow.pac |= pac;
+                    }
+                    GnuProperty::Other { .. } => {}
+                }
+            }
+        }
+
+        SecurityReport {
+            relro,
+            position_independent,
+            nx_stack,
+            text_relocations,
+            stack_protector,
+            fortified_imports,
+            rpath: self.search_path(DynamicSectionType::DT_RPATH),
+            runpath: self.search_path(DynamicSectionType::DT_RUNPATH),
+            control_flow,
+        }
+    }
+
+    /// Names of the undefined dynamic symbols, the ones the library imports
+    fn imports(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        let string_table = self.string_table();
+        let symbols = self.symbols();
+        let count = symbols.map_or(0, |symbols| symbols.entries().len());
+        (1..count).filter_map(move |index| {
+            let symbols = symbols?;
+            let symbol = symbols.entries().get(index)?;
+            if symbol.is_defined() || symbol.st_name == 0 {
+                return None;
+            }
+            symbols
+                .resolve_cstr(index, string_table)
+                .map(CStr::to_bytes)
+        })
+    }
+}
@@ -0,0 +1,188 @@
+/// A shell style glob pattern matched against raw symbol names.
+/// Supports `*`, `?`, character classes such as `[a-z]` or `[!0-9]` and `\` escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glob<'a> {
+    pattern: &'a [u8],
+}
+
+impl<'a> Glob<'a> {
+    /// Create a glob from its pattern, every pattern is valid
+    pub fn new(pattern: &'a str) -> Self {
+        Self {
+            pattern: pattern.as_bytes(),
+        }
+    }
+
+    /// The glob's pattern
+    pub fn pattern(&self) -> &'a [u8] {
+        self.pattern
+    }
+
+    /// Test the entirety of `text` against the pattern
+    pub fn matches(&self, text: &[u8]) -> bool {
+        let pattern = self.pattern;
+        let (mut p, mut t) = (0, 0);
+        // The most recent `*` and the text position it is currently absorbing up to
+        let mut backtrack: Option<(usize, usize)> = None;
+
+        while t < text.len() {
+            if let Some((consumed, matched)) = match_token(&pattern[p..], text[t]) {
+                if matched {
+                    p += consumed;
+                    t += 1;
+                    continue;
+                }
+            } else if pattern.get(p) == Some(&b'*') {
+                backtrack = Some((p, t));
+                p += 1;
+                continue;
+            }
+
+            // Mismatch, let the last star absorb one more character
+            match backtrack {
+                Some((star, absorbed)) => {
+                    backtrack = Some((star, absorbed + 1));
+                    p = star + 1;
+                    t = absorbed + 1;
+                }
+                None => return false,
+            }
+        }
+
+        pattern[p..].iter().all(|c| *c == b'*')
+    }
+}
+
+/// Match a single non star token at the start of `pattern` against `c`.
+/// Returns the length of the token and whether it matched, or None for a star or an exhausted pattern.
+fn match_token(pattern: &[u8], c: u8) -> Option<(usize, bool)> {
+    match *pattern.first()? {
+        b'*' => None,
+        b'?' => Some((1, true)),
+        b'\\' if pattern.len() > 1 => Some((2, pattern[1] == c)),
+        b'[' => Some(match_class(pattern, c).unwrap_or((1, c == b'['))),
+        literal => Some((1, literal == c)),
+    }
+}
+
+/// Match a `[...]` character class, None if the class is unterminated and `[` should be taken literally
+fn match_class(pattern: &[u8], c: u8) -> Option<(usize, bool)> {
+    let mut i = 1;
+    let negated = matches!(pattern.get(i), Some(b'!' | b'^'));
+    if negated {
+        i += 1;
+    }
+
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let start = *pattern.get(i)?;
+        if start == b']' && !first {
+            return Some((i + 1, matched != negated));
+        }
+        first = false;
+
+        if pattern.get(i + 1) == Some(&b'-') && pattern.get(i + 2).is_some_and(|end| *end != b']') {
+            matched |= (start..=pattern[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= start == c;
+            i += 1;
+        }
+    }
+}
+
+/// How a selector matches the symbol name itself
+#[derive(Debug, Clone)]
+enum NamePattern<'a> {
+    Exact(&'a [u8]),
+    Glob(Glob<'a>),
+    #[cfg(feature = "regex")]
+    Regex(regex::bytes::Regex),
+}
+
+/// Selects imported symbols by exact name, glob or regular expression, optionally restricted to a symbol version.
+///
+/// ```
+/// use plt_rs::SymbolSelector;
+///
+/// let selector = SymbolSelector::glob("pthread_mutex_*");
+/// assert!(selector.matches(b"pthread_mutex_lock", None));
+///
+/// let selector = SymbolSelector::glob("*@GLIBC_2.17");
+/// assert!(selector.matches(b"clock_gettime", Some(b"GLIBC_2.17")));
+/// assert!(!selector.matches(b"clock_gettime", None));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SymbolSelector<'a> {
+    name: NamePattern<'a>,
+    version: Option<Glob<'a>>,
+}
+
+/// Split `pattern` into its name and version at the first `@`, accepting the `@@` default version notation
+fn split_version(pattern: &str) -> (&str, Option<&str>) {
+    match pattern.split_once('@') {
+        Some((name, version)) => (name, Some(version.trim_start_matches('@'))),
+        None => (pattern, None),
+    }
+}
+
+impl<'a> SymbolSelector<'a> {
+    /// Select the symbol named exactly `name`, of any version
+    pub fn exact(name: &'a str) -> Self {
+        Self {
+            name: NamePattern::Exact(name.as_bytes()),
+            version: None,
+        }
+    }
+
+    /// Select symbols matching the glob `pattern`.
+    /// A `name@version` pattern additionally globs the symbol's version, e.g. `*@GLIBC_2.17`.
+    pub fn glob(pattern: &'a str) -> Self {
+        let (name, version) = split_version(pattern);
+        Self {
+            name: NamePattern::Glob(Glob::new(name)),
+            version: version.map(Glob::new),
+        }
+    }
+
+    /// Select symbols whose name matches the regular expression `pattern`, e.g. `^__.*_chk$`.
+    /// The version is not part of the matched text, restrict it with `with_version`.
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self {
+            name: NamePattern::Regex(regex::bytes::Regex::new(pattern)?),
+            version: None,
+        })
+    }
+
+    /// Only select symbols whose version matches the glob `pattern`
+    pub fn with_version(mut self, pattern: &'a str) -> Self {
+        self.version = Some(Glob::new(pattern));
+        self
+    }
+
+    /// Test a symbol's name and version against the selector.
+    /// Unversioned symbols never match a selector with a version pattern.
+    pub fn matches(&self, name: &[u8], version: Option<&[u8]>) -> bool {
+        let name_matches = match &self.name {
+            NamePattern::Exact(exact) => *exact == name,
+            NamePattern::Glob(glob) => glob.matches(name),
+            #[cfg(feature = "regex")]
+            NamePattern::Regex(regex) => regex.is_match(name),
+        };
+
+        name_matches
+            && match (&self.version, version) {
+                (None, _) => true,
+                (Some(pattern), Some(version)) => pattern.matches(version),
+                (Some(_), None) => false,
+            }
+    }
+}
+
+impl<'a> From<&'a str> for SymbolSelector<'a> {
+    fn from(name: &'a str) -> Self {
+        Self::exact(name)
+    }
+}
@@ -0,0 +1,232 @@
+use super::{
+    checked_slice, rebase, DynamicError, DynamicSection, DynamicSectionType, LoadedLibrary,
+    StringTable,
+};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+use core::ffi::CStr;
+use core::mem::size_of;
+
+/// Version index reserved for local symbols
+const VER_NDX_LOCAL: u16 = 0;
+/// Version index reserved for unversioned global symbols
+const VER_NDX_GLOBAL: u16 = 1;
+/// The hidden bit of a DT_VERSYM entry, not part of the index
+const VERSYM_HIDDEN: u16 = 0x8000;
+
+/// Version dependency entry, the same layout for 32 and 64 bit elfs
+#[repr(C)]
+#[derive(Debug)]
+pub struct Verneed {
+    pub vn_version: u16,
+    pub vn_cnt: u16,
+    pub vn_file: u32,
+    pub vn_aux: u32,
+    pub vn_next: u32,
+}
+
+/// Auxiliary version dependency entry, one per required version of a Verneed
+#[repr(C)]
+#[derive(Debug)]
+pub struct Vernaux {
+    pub vna_hash: u32,
+    pub vna_flags: u16,
+    pub vna_other: u16,
+    pub vna_name: u32,
+    pub vna_next: u32,
+}
+
+/// Version definition entry, the same layout for 32 and 64 bit elfs
+#[repr(C)]
+#[derive(Debug)]
+pub struct Verdef {
+    pub vd_version: u16,
+    pub vd_flags: u16,
+    pub vd_ndx: u16,
+    pub vd_cnt: u16,
+    pub vd_hash: u32,
+    pub vd_aux: u32,
+    pub vd_next: u32,
+}
+
+/// Auxiliary version definition entry, the first one names the defined version
+#[repr(C)]
+#[derive(Debug)]
+pub struct Verdaux {
+    pub vda_name: u32,
+    pub vda_next: u32,
+}
+
+/// A view of the library's GNU symbol versioning tables.
+/// DT_VERSYM is parallel to the dynamic symbol table and indexes into the versions
+/// either required through DT_VERNEED or defined through DT_VERDEF.
+#[derive(Clone, Copy)]
+pub struct SymbolVersions<'a> {
+    versym: &'a [u16],
+    /// The version records, up to the end of the segment holding them, along with how many there are
+    verneed: Option<(&'a [u8], usize)>,
+    verdef: Option<(&'a [u8], usize)>,
+}
+
+/// Read the record at `offset` into `table`, if it lies within the table and is aligned
+fn record<T>(table: &[u8], offset: usize) -> Option<&T> {
+    let bytes = table.get(offset..offset.checked_add(size_of::<T>())?)?;
+    let ptr = bytes.as_ptr().cast::<T>();
+    match ptr.is_aligned() {
+        true => Some(unsafe { &*ptr }),
+        false => None,
+    }
+}
+
+impl<'a> SymbolVersions<'a> {
+    /// Access the version index of the dynamic symbol at `symbol_index`, with the hidden bit masked.
+    /// Symbols past the end of the table are reported as unversioned globals.
+    pub fn version_index(&self, symbol_index: usize) -> u16 {
+        self.versym
+            .get(symbol_index)
+            .map_or(VER_NDX_GLOBAL, |entry| entry & !VERSYM_HIDDEN)
+    }
+
+    /// Is the dynamic symbol at `symbol_index` a non default version of its name, `name@VERSION` rather than
+    /// `name@@VERSION`, which only binds references asking for that version
+    pub fn is_hidden(&self, symbol_index: usize) -> bool {
+        self.versym
+            .get(symbol_index)
+            .is_some_and(|entry| entry & VERSYM_HIDDEN != 0)
+    }
+
+    /// Find the string table offset naming the version `index`.
+    /// Every record is bounds checked, a malformed chain ends the search.
+    fn version_name_offset(&self, index: u16) -> Option<usize> {
+        if let Some((verneed, count)) = self.verneed {
+            let mut current = 0usize;
+            for _ in 0..count {
+                let need = record::<Verneed>(verneed, current)?;
+                let mut aux_offset = current.checked_add(need.vn_aux as usize)?;
+                for _ in 0..need.vn_cnt {
+                    let aux = record::<Vernaux>(verneed, aux_offset)?;
+                    if aux.vna_other & !VERSYM_HIDDEN == index {
+                        return Some(aux.vna_name as usize);
+                    }
+                    aux_offset = aux_offset.checked_add(aux.vna_next as usize)?;
+                }
+                if need.vn_next == 0 {
+                    break;
+                }
+                current = current.checked_add(need.vn_next as usize)?;
+            }
+        }
+
+        if let Some((verdef, count)) = self.verdef {
+            let mut current = 0usize;
+            for _ in 0..count {
+                let def = record::<Verdef>(verdef, current)?;
+                if def.vd_ndx & !VERSYM_HIDDEN == index && def.vd_cnt > 0 {
+                    let aux = record::<Verdaux>(verdef, current.checked_add(def.vd_aux as usize)?)?;
+                    return Some(aux.vda_name as usize);
+                }
+                if def.vd_next == 0 {
+                    break;
+                }
+                current = current.checked_add(def.vd_next as usize)?;
+            }
+        }
+
+        None
+    }
+
+    /// Resolves the version name of the dynamic symbol at `symbol_index` without allocating
+    pub fn resolve_version_cstr<'b>(
+        &self,
+        symbol_index: usize,
+        string_table: &StringTable<'b>,
+    ) -> Option<&'b CStr> {
+        match self.version_index(symbol_index) {
+            VER_NDX_LOCAL | VER_NDX_GLOBAL => None,
+            index => string_table.read_cstr_at(self.version_name_offset(index)?),
+        }
+    }
+
+    /// Resolves the version name of the dynamic symbol at `symbol_index`, such as `GLIBC_2.17`.
+    /// Local and unversioned global symbols have no version.
+    #[cfg(feature = "alloc")]
+    pub fn resolve_version<'b>(
+        &'b self,
+        symbol_index: usize,
+        string_table: &'b StringTable<'b>,
+    ) -> Option<Cow<'b, str>> {
+        match self.version_index(symbol_index) {
+            VER_NDX_LOCAL | VER_NDX_GLOBAL => None,
+            index => string_table.read_at(self.version_name_offset(index)?),
+        }
+    }
+}
+
+/// The bytes from the version records referenced through `tag` to the end of the segment holding them
+fn version_records<'b>(
+    lib: &LoadedLibrary<'_>,
+    tag: DynamicSectionType,
+    d_val_ptr: usize,
+) -> Result<&'b [u8], DynamicError> {
+    let addr = rebase(lib, d_val_ptr);
+    let size = lib
+        .segment_remaining(addr)
+        .ok_or(DynamicError::OutOfBounds(tag))?;
+    checked_slice(lib, addr, size).ok_or(DynamicError::OutOfBounds(tag))
+}
+
+/// Access the libraries symbol versioning tables through the library's dynamic section.
+/// DT_VERSYM holds one entry per dynamic symbol, `symbol_count` of them.
+pub(crate) fn extract_symbol_versions<'a, 'b>(
+    lib: &'a LoadedLibrary<'a>,
+    dynamic_section: &'a DynamicSection<'a>,
+    symbol_count: usize,
+) -> Result<Option<SymbolVersions<'b>>, DynamicError> {
+    // Versioning is optional, without DT_VERSYM nothing else is relevant.
+    let Some(versym_entry) = dynamic_section.find_section(DynamicSectionType::DT_VERSYM) else {
+        return Ok(None);
+    };
+
+    let verneed = match dynamic_section.find_section(DynamicSectionType::DT_VERNEED) {
+        Some(entry) => {
+            let count = dynamic_section
+                .find_section(DynamicSectionType::DT_VERNEEDNUM)
+                .ok_or(DynamicError::DependentSection(
+                    DynamicSectionType::DT_VERNEED,
+                    DynamicSectionType::DT_VERNEEDNUM,
+                ))?
+                .d_val_ptr as usize;
+            let records = version_records(
+                lib,
+                DynamicSectionType::DT_VERNEED,
+                entry.d_val_ptr as usize,
+            )?;
+            Some((records, count))
+        }
+        None => None,
+    };
+
+    let verdef = match dynamic_section.find_section(DynamicSectionType::DT_VERDEF) {
+        Some(entry) => {
+            let count = dynamic_section
+                .find_section(DynamicSectionType::DT_VERDEFNUM)
+                .ok_or(DynamicError::DependentSection(
+                    DynamicSectionType::DT_VERDEF,
+                    DynamicSectionType::DT_VERDEFNUM,
+                ))?
+                .d_val_ptr as usize;
+            let records =
+                version_records(lib, DynamicSectionType::DT_VERDEF, entry.d_val_ptr as usize)?;
+            Some((records, count))
+        }
+        None => None,
+    };
+
+    let versym = rebase(lib, versym_entry.d_val_ptr as usize);
+    Ok(Some(SymbolVersions {
+        versym: checked_slice(lib, versym, symbol_count)
+            .ok_or(DynamicError::OutOfBounds(DynamicSectionType::DT_VERSYM))?,
+        verneed,
+        verdef,
+    }))
+}
@@ -0,0 +1,93 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ops::ControlFlow;
+
+use plt_rs::{collect_modules, DynamicLibrary, ModuleKind, SymbolSelector};
+
+/// Counts the allocations made by the current thread while tracking is enabled
+struct CountingAllocator;
+
+thread_local! {
+    static TRACKING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if TRACKING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Run `f` and return how many allocations it made on this thread
+fn count_allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    TRACKING.with(|tracking| tracking.set(true));
+    f();
+    TRACKING.with(|tracking| tracking.set(false));
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Name lookups have to be usable from within a hooked allocator
+#[test]
+fn symbol_lookups_do_not_allocate() {
+    let modules = collect_modules();
+    let libraries = modules
+        .iter()
+        .filter_map(|module| module.dynamic().ok())
+        .collect::<Vec<_>>();
+    let selector = SymbolSelector::glob("mall?c@*");
+
+    let allocations = count_allocations(|| {
+        for dyn_lib in libraries.iter() {
+            let _ = dyn_lib.try_find_function("malloc");
+            for relocation in dyn_lib.find_hookable_relocations("malloc") {
+                std::hint::black_box(relocation);
+            }
+            for selected in dyn_lib.select_relocations(&selector) {
+                std::hint::black_box(selected.name.to_bytes());
+            }
+        }
+    });
+    assert_eq!(allocations, 0);
+}
+
+unsafe fn hooked_getpid() -> libc::pid_t {
+    999
+}
+
+/// Everything from module enumeration through to patching has to be usable from within a hooked allocator
+#[test]
+fn module_visit_and_hook_do_not_allocate() {
+    let mut hooked_pid = 0;
+
+    let allocations = count_allocations(|| {
+        let found = plt_rs::for_each_module(|lib| {
+            if lib.kind() != ModuleKind::MainProgram {
+                return ControlFlow::Continue(());
+            }
+
+            let dyn_lib = DynamicLibrary::initialize(lib).expect("can load");
+            for relocation in dyn_lib.find_hookable_relocations("getpid") {
+                let slot = dyn_lib.relocation_addr(&relocation);
+                let previous =
+                    plt_rs::patch(slot, hooked_getpid as *const () as usize).expect("can patch");
+                hooked_pid = unsafe { libc::getpid() };
+                plt_rs::patch(slot, previous).expect("can restore");
+            }
+            ControlFlow::Break(())
+        });
+        assert!(found.is_some());
+    });
+    assert_eq!(hooked_pid, 999);
+    assert_eq!(allocations, 0);
+}
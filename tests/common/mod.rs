@@ -0,0 +1,168 @@
+//! A hand made module for exercising the parser on layouts the test process does not load,
+//! and the C fixtures built on demand for the layouts compilers produce
+#![allow(dead_code)]
+
+#[cfg(target_pointer_width = "32")]
+pub use plt_rs::elf32 as elf;
+#[cfg(target_pointer_width = "64")]
+pub use plt_rs::elf64 as elf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const DT_NULL: usize = 0;
+pub const DT_HASH: usize = 4;
+pub const DT_STRTAB: usize = 5;
+pub const DT_SYMTAB: usize = 6;
+pub const DT_STRSZ: usize = 10;
+pub const DT_SYMENT: usize = 11;
+pub const DT_RELA: usize = 7;
+pub const DT_RELASZ: usize = 8;
+
+pub const PT_LOAD: u32 = 1;
+pub const PT_DYNAMIC: u32 = 2;
+
+pub const ET_EXEC: u16 = 2;
+pub const ET_DYN: u16 = 3;
+
+pub const DYNAMIC: usize = 64;
+pub const HASH: usize = 256;
+pub const SYMTAB: usize = 320;
+pub const STRTAB: usize = 448;
+pub const IMAGE_SIZE: usize = 512;
+
+/// The image, laid out as a single segment starting with its elf header
+#[repr(C, align(16))]
+pub struct Image([u8; IMAGE_SIZE]);
+
+impl Image {
+    /// An image without an elf header, holding `dynamic` along with a hash table, symbol table and string table
+    /// describing a single `getpid` symbol
+    pub fn new(dynamic: &[(usize, usize)]) -> Box<Self> {
+        let mut image = Box::new(Image([0; IMAGE_SIZE]));
+        image.write_dynamic(dynamic);
+        for (index, word) in [1u32, 2, 1, 0, 0].iter().enumerate() {
+            image.put(HASH + index * size_of::<u32>(), *word);
+        }
+        image.put(
+            SYMTAB + size_of::<elf::DynSym>(),
+            elf::DynSym {
+                st_name: 1,
+                st_info: 0x12,
+                st_other: 0,
+                st_shndx: 0,
+                st_value: 0,
+                st_size: 0,
+            },
+        );
+        image.0[STRTAB..STRTAB + 8].copy_from_slice(b"\0getpid\0");
+        image
+    }
+
+    /// Write an elf header of type `e_type` at the start of the image
+    pub fn with_elf_type(self: Box<Self>, e_type: u16) -> Box<Self> {
+        self.with_elf_header(e_type, 0)
+    }
+
+    /// Write an elf header of type `e_type` for `e_machine` at the start of the image
+    pub fn with_elf_header(mut self: Box<Self>, e_type: u16, e_machine: u16) -> Box<Self> {
+        let mut e_ident = [0; 16];
+        e_ident[..4].copy_from_slice(b"\x7fELF");
+        self.put(
+            0,
+            elf::FileHeader {
+                e_ident,
+                e_type,
+                e_machine,
+                e_version: 1,
+                e_entry: 0,
+                e_phoff: 0,
+                e_shoff: 0,
+                e_flags: 0,
+                e_ehsize: size_of::<elf::FileHeader>() as _,
+                e_phentsize: size_of::<elf::ProgramHeader>() as _,
+                e_phnum: 0,
+                e_shentsize: 0,
+                e_shnum: 0,
+                e_shstrndx: 0,
+            },
+        );
+        self
+    }
+
+    /// Overwrite the start of the dynamic section with `dynamic`
+    pub fn write_dynamic(&mut self, dynamic: &[(usize, usize)]) {
+        for (index, (tag, value)) in dynamic.iter().enumerate() {
+            self.put(DYNAMIC + index * 2 * size_of::<usize>(), *tag);
+            self.put(DYNAMIC + (index * 2 + 1) * size_of::<usize>(), *value);
+        }
+    }
+
+    pub fn put<T>(&mut self, offset: usize, value: T) {
+        assert!(offset + size_of::<T>() <= IMAGE_SIZE);
+        unsafe {
+            self.0
+                .as_mut_ptr()
+                .add(offset)
+                .cast::<T>()
+                .write_unaligned(value)
+        };
+    }
+
+    pub fn addr(&self) -> usize {
+        self.0.as_ptr() as usize
+    }
+}
+
+pub fn header(p_type: u32, vaddr: usize, size: usize) -> elf::ProgramHeader {
+    header_at(p_type, vaddr, vaddr, size)
+}
+
+/// A program header for `size` bytes at file `offset`, linked at `vaddr`
+pub fn header_at(p_type: u32, offset: usize, vaddr: usize, size: usize) -> elf::ProgramHeader {
+    elf::ProgramHeader {
+        p_type,
+        p_flags: 4,
+        p_offset: offset as _,
+        p_vaddr: vaddr as _,
+        p_paddr: vaddr as _,
+        p_filesz: size as _,
+        p_memsz: size as _,
+        p_align: 16,
+    }
+}
+
+/// A well formed dynamic section, with every table address relative to the start of the image
+pub fn well_formed() -> Vec<(usize, usize)> {
+    well_formed_at(0)
+}
+
+/// A well formed dynamic section, with the image's tables addressed as if it started at `start`
+pub fn well_formed_at(start: usize) -> Vec<(usize, usize)> {
+    vec![
+        (DT_HASH, start.wrapping_add(HASH)),
+        (DT_SYMTAB, start.wrapping_add(SYMTAB)),
+        (DT_SYMENT, size_of::<elf::DynSym>()),
+        (DT_STRTAB, start.wrapping_add(STRTAB)),
+        (DT_STRSZ, 8),
+        (DT_NULL, 0),
+    ]
+}
+
+/// Compile `source` from `tests/fixtures` into the shared library `name` with `flags`.
+/// Returns None, reporting the skip, if no C compiler is available or it rejects the flags.
+pub fn build_fixture(name: &str, source: &str, flags: &[&str]) -> Option<PathBuf> {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let output = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let built = Command::new("cc")
+        .args(["-shared", "-fPIC", "-O1", "-o"])
+        .arg(&output)
+        .arg(fixtures.join(source))
+        .args(flags)
+        .status()
+        .is_ok_and(|status| status.success());
+    if !built {
+        eprintln!("skipping: unable to build {name} from {source} with cc");
+        return None;
+    }
+    Some(output)
+}
@@ -0,0 +1,144 @@
+#![cfg(all(
+    target_env = "gnu",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+mod common;
+
+use common::*;
+use plt_rs::{
+    find_module_by_name, main_executable, DataError, DynamicLibrary, LoadedLibrary, RelocationKind,
+};
+
+#[cfg(target_arch = "x86_64")]
+const R_COPY: usize = 5;
+#[cfg(target_arch = "aarch64")]
+const R_COPY: usize = 1024;
+
+const DT_RELAENT: usize = 9;
+const RELA: usize = 464;
+const COPY: usize = 496;
+
+fn global_symbol(name: &core::ffi::CStr) -> usize {
+    unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) as usize }
+}
+
+#[test]
+fn reports_got_references_to_data() {
+    let module = main_executable().expect("main executable");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let environ = dyn_lib
+        .data_relocations()
+        .find(|data| data.name == c"environ")
+        .expect("std reads environ");
+    assert_eq!(environ.relocation.kind(), RelocationKind::GlobDat);
+    assert_eq!(environ.size, 0, "undefined references carry no size");
+    assert_eq!(environ.object, global_symbol(c"environ"));
+    assert_eq!(dyn_lib.data_definition(&environ), Some(environ.object));
+
+    // Functions are not data
+    assert!(dyn_lib
+        .data_relocations()
+        .all(|data| data.name != c"getpid"));
+}
+
+#[test]
+fn reports_copy_relocations() {
+    // An executable holding a copy of environ at COPY
+    let mut dynamic = well_formed();
+    dynamic.pop();
+    dynamic[4].1 = 9;
+    dynamic.extend([
+        (DT_RELA, RELA),
+        (DT_RELASZ, size_of::<elf::DynRela>()),
+        (DT_RELAENT, size_of::<elf::DynRela>()),
+        (DT_NULL, 0),
+    ]);
+    let mut image = Image::new(&dynamic);
+    image.put(
+        SYMTAB + size_of::<elf::DynSym>(),
+        elf::DynSym {
+            st_name: 1,
+            st_info: 0x11,
+            st_other: 0,
+            st_shndx: 1,
+            st_value: COPY as _,
+            st_size: 8,
+        },
+    );
+    for (index, byte) in b"\0environ\0".iter().enumerate() {
+        image.put(STRTAB + index, *byte);
+    }
+    image.put(
+        RELA,
+        elf::DynRela {
+            r_offset: COPY as _,
+            r_info: (1 << 32 | R_COPY) as _,
+            r_addend: 0,
+        },
+    );
+
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+
+    let copies = dyn_lib.data_relocations().collect::<Vec<_>>();
+    assert_eq!(copies.len(), 1);
+    let copy = copies[0];
+    assert_eq!(copy.relocation.kind(), RelocationKind::Copy);
+    assert_eq!(copy.name, c"environ");
+    assert_eq!(copy.size, 8);
+    assert_eq!(copy.object, image.addr() + COPY);
+    assert_eq!(
+        dyn_lib.data_definition(&copy),
+        Some(global_symbol(c"environ"))
+    );
+
+    assert!(matches!(
+        dyn_lib.redirect_data(&copy.relocation, 0),
+        Err(DataError::NotAGotEntry(RelocationKind::Copy))
+    ));
+}
+
+#[test]
+fn redirects_data_references() {
+    let Some(output) = build_fixture("libdata_refs.so", "data_refs.c", &[]) else {
+        return;
+    };
+
+    let path = std::ffi::CString::new(output.as_os_str().as_encoded_bytes()).unwrap();
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load fixture");
+    let read_stdout: extern "C" fn() -> usize =
+        unsafe { std::mem::transmute(libc::dlsym(handle, c"data_refs_stdout".as_ptr())) };
+
+    let module = find_module_by_name("libdata_refs.so").expect("fixture is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let stdout = dyn_lib
+        .data_relocations()
+        .find(|data| data.name == c"stdout")
+        .expect("fixture reads stdout");
+    assert_eq!(stdout.object, global_symbol(c"stdout"));
+    assert_eq!(dyn_lib.data_definition(&stdout), Some(stdout.object));
+    let real = read_stdout();
+
+    static STORAGE: usize = 0x5107;
+    let storage = &STORAGE as *const usize as usize;
+    let previous = dyn_lib
+        .redirect_data(&stdout.relocation, storage)
+        .expect("can redirect");
+    assert_eq!(previous, stdout.object);
+    assert_eq!(read_stdout(), STORAGE);
+
+    dyn_lib
+        .redirect_data(&stdout.relocation, previous)
+        .expect("can restore");
+    assert_eq!(read_stdout(), real);
+
+    unsafe { libc::dlclose(handle) };
+}
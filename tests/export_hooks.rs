@@ -0,0 +1,68 @@
+#![cfg(target_env = "gnu")]
+
+mod common;
+
+use common::build_fixture;
+use plt_rs::{find_module_by_name, ExportError};
+
+extern "C" fn hooked_answer() -> i32 {
+    7
+}
+
+#[test]
+fn hooks_exports_for_later_bindings() {
+    if std::env::var_os("LD_BIND_NOW").is_some() {
+        return;
+    }
+    let Some(output) = build_fixture("libexports.so", "exports.c", &["-Wl,-z,lazy"]) else {
+        return;
+    };
+
+    let path = std::ffi::CString::new(output.as_os_str().as_encoded_bytes()).unwrap();
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_LAZY | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load fixture");
+    let lookup = |name: &core::ffi::CStr| unsafe { libc::dlsym(handle, name.as_ptr()) as usize };
+    let original = lookup(c"exports_answer");
+    let call_answer: extern "C" fn() -> i32 =
+        unsafe { std::mem::transmute(lookup(c"exports_call_answer")) };
+
+    let module = find_module_by_name("libexports.so").expect("fixture is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let hook = dyn_lib
+        .hook_export("exports_answer", hooked_answer as *const () as usize)
+        .expect("can hook");
+    assert_eq!(hook.original(), original);
+    assert_eq!(
+        lookup(c"exports_answer"),
+        hooked_answer as *const () as usize
+    );
+    // The fixture's own slot is bound on its first call, after the hook
+    assert_eq!(call_answer(), 8);
+
+    dyn_lib.unhook_export(hook).expect("can unhook");
+    assert_eq!(lookup(c"exports_answer"), original);
+    // Bound while hooked, the slot keeps the hook
+    assert_eq!(call_answer(), 8);
+
+    unsafe { libc::dlclose(handle) };
+}
+
+#[test]
+fn rejects_unhookable_exports() {
+    let module = find_module_by_name("libc.so.6").expect("libc is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+    assert!(matches!(
+        dyn_lib.hook_export("plt_rs_no_such_symbol", 0),
+        Err(ExportError::NotDefined)
+    ));
+    // An import of libc, not a definition
+    assert!(matches!(
+        dyn_lib.hook_export("__tls_get_addr", 0),
+        Err(ExportError::NotDefined)
+    ));
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    assert!(matches!(
+        dyn_lib.hook_export("memcpy", 0),
+        Err(ExportError::IndirectFunction)
+    ));
+}
@@ -0,0 +1,12 @@
+#include <stdio.h>
+
+extern char **environ;
+
+/* Reads data objects defined by libc through GOT entries */
+FILE *data_refs_stdout(void) {
+    return stdout;
+}
+
+char **data_refs_environ(void) {
+    return environ;
+}
@@ -0,0 +1,8 @@
+/* Exports a function and calls it through its own PLT, as default visibility makes it interposable */
+int exports_answer(void) {
+    return 42;
+}
+
+int exports_call_answer(void) {
+    return exports_answer() + 1;
+}
@@ -0,0 +1,13 @@
+/* Exported constructor and destructor, so their names are in the dynamic symbol table */
+int init_fini_state;
+void (*init_fini_on_destroy)(void);
+
+__attribute__((constructor)) void init_fini_constructor(void) {
+    init_fini_state = 1;
+}
+
+__attribute__((destructor)) void init_fini_destructor(void) {
+    if (init_fini_on_destroy) {
+        init_fini_on_destroy();
+    }
+}
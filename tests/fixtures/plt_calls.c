@@ -0,0 +1,16 @@
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+/* Calls a handful of libc functions through the PLT */
+int plt_calls(const char *text) {
+    char *copy = malloc(strlen(text) + 1);
+    if (copy == NULL) {
+        return -1;
+    }
+    strcpy(copy, text);
+    puts(copy);
+    int length = abs((int)strlen(copy));
+    free(copy);
+    return length;
+}
@@ -0,0 +1,82 @@
+#![cfg(target_env = "gnu")]
+
+use plt_rs::{find_module_by_name, main_executable, DynamicLibrary, GotError, SegmentType};
+
+/// Built for lazy binding, so glibc fills in its reserved GOT entries.
+/// Kept in its own test binary, its resolver entry is overwritten while a test runs.
+const LIBRARY: &core::ffi::CStr = c"libresolv.so.2";
+
+/// Overwrite the word at `addr`, which may lie in PT_GNU_RELRO and be read only after relocation.
+/// Such a page is made writable for the write and handed back read only, other pages are written in place.
+fn overwrite(dyn_lib: &DynamicLibrary<'_>, addr: usize, value: usize) {
+    let library = dyn_lib.library();
+    let relro = library.program_headers().any(|p_h| {
+        p_h.segment_type() == SegmentType::GnuRelro
+            && p_h.absolute_range(library.addr()).contains(&addr)
+    });
+    let slot = addr as *mut usize;
+    if !relro {
+        unsafe { slot.write_volatile(value) };
+        return;
+    }
+
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGE_SIZE) as usize };
+    let page = (addr / page_size * page_size) as *mut libc::c_void;
+    let writable = libc::PROT_READ | libc::PROT_WRITE;
+    assert_eq!(unsafe { libc::mprotect(page, page_size, writable) }, 0);
+    unsafe { slot.write_volatile(value) };
+    assert_eq!(
+        unsafe { libc::mprotect(page, page_size, libc::PROT_READ) },
+        0
+    );
+}
+
+#[test]
+fn immediately_bound_executable_got() {
+    let executable = main_executable().expect("main executable");
+    let dyn_lib = executable.dynamic().expect("can parse");
+    let Some(got) = dyn_lib.reserved_got() else {
+        // Linked without a PLT GOT at all
+        assert!(dyn_lib.got_addr().is_none());
+        return;
+    };
+    assert!(dyn_lib.library().contains_addr(got.addr));
+    assert_eq!(
+        executable.addr() + got.dynamic,
+        dyn_lib.dyn_section().entries().as_ptr() as usize
+    );
+    assert_eq!(dyn_lib.verify_got().expect("consistent"), got);
+}
+
+#[test]
+fn lazily_bound_library_got() {
+    if std::env::var_os("LD_BIND_NOW").is_some() {
+        return;
+    }
+
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_LAZY | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load test library");
+    let module = find_module_by_name("libresolv.so.2").expect("libresolv");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let got = dyn_lib.verify_got().expect("consistent");
+    assert!(got.is_lazy_binding_set_up());
+    // glibc's dlopen handles are the module's link_map
+    assert_eq!(got.link_map, handle as usize);
+
+    // Hijack the resolver entry, then put it back
+    let resolver_slot = got.addr + 2 * size_of::<usize>();
+    let hijacked = lazily_bound_library_got as *const () as usize;
+    overwrite(&dyn_lib, resolver_slot, hijacked);
+    let verified = dyn_lib.verify_got();
+    overwrite(&dyn_lib, resolver_slot, got.resolver);
+    assert!(matches!(verified, Err(GotError::HijackedResolver(addr)) if addr == hijacked));
+
+    let link_map_slot = got.addr + size_of::<usize>();
+    overwrite(&dyn_lib, link_map_slot, hijacked);
+    let verified = dyn_lib.verify_got();
+    overwrite(&dyn_lib, link_map_slot, got.link_map);
+    assert!(matches!(verified, Err(GotError::LinkMapMismatch(addr)) if addr == hijacked));
+
+    assert_eq!(dyn_lib.verify_got().expect("restored"), got);
+}
@@ -0,0 +1,69 @@
+#![cfg(all(
+    target_env = "gnu",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+use plt_rs::{find_module_by_name, main_executable, HookReason, RelocationKind};
+
+#[test]
+fn explains_plt_slots() {
+    // Not linked by the test binary, libresolv calls free through its plt
+    let handle = unsafe { libc::dlopen(c"libresolv.so.2".as_ptr(), libc::RTLD_LAZY) };
+    assert!(!handle.is_null(), "unable to load test library");
+    let module = find_module_by_name("libresolv.so.2").expect("libresolv");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let diagnosis = dyn_lib.explain_hook("free");
+    assert!(matches!(diagnosis.reason, HookReason::PltSlot));
+    assert!(diagnosis.is_hookable());
+    // Lazily bound slots stay writable
+    assert!(!diagnosis.read_only);
+    assert!(diagnosis
+        .relocations
+        .iter()
+        .any(|reloc| reloc.kind() == RelocationKind::JumpSlot));
+}
+
+#[test]
+fn explains_got_only_imports() {
+    // Rust calls imports through the GOT rather than the plt
+    let module = main_executable().expect("main executable");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let diagnosis = dyn_lib.explain_hook("getpid");
+    let relocations = &diagnosis.relocations;
+    if relocations
+        .iter()
+        .any(|reloc| reloc.kind() == RelocationKind::JumpSlot)
+    {
+        return;
+    }
+    assert!(matches!(diagnosis.reason, HookReason::GotOnly));
+    assert!(diagnosis.is_hookable());
+    // Rust links with full RELRO
+    assert!(diagnosis.read_only);
+    assert!(relocations
+        .iter()
+        .all(|reloc| reloc.kind() == RelocationKind::GlobDat));
+}
+
+#[test]
+fn explains_unhookable_symbols() {
+    let module = find_module_by_name("libc.so.6").expect("libc is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let diagnosis = dyn_lib.explain_hook("getpid");
+    assert!(matches!(diagnosis.reason, HookReason::DefinedLocally));
+    assert!(diagnosis.relocations.is_empty());
+
+    let diagnosis = dyn_lib.explain_hook("strlen");
+    assert!(matches!(diagnosis.reason, HookReason::IfuncOnly));
+    assert!(!diagnosis.is_hookable());
+    assert!(diagnosis
+        .relocations
+        .iter()
+        .all(|reloc| reloc.kind() == RelocationKind::IRelative));
+
+    let diagnosis = dyn_lib.explain_hook("plt_rs_no_such_symbol");
+    assert!(matches!(diagnosis.reason, HookReason::NotImported));
+    assert!(diagnosis.relocations.is_empty());
+}
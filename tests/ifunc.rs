@@ -0,0 +1,50 @@
+#![cfg(all(
+    target_env = "gnu",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+use plt_rs::find_module_by_name;
+
+/// Address `dlsym` finds for `name` in the global scope, ifuncs resolve to their implementation
+fn global_symbol(name: &core::ffi::CStr) -> usize {
+    unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) as usize }
+}
+
+#[test]
+fn reports_libc_ifunc_relocations() {
+    let module = find_module_by_name("libc.so.6").expect("libc is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let library = dyn_lib.library();
+
+    let ifuncs = dyn_lib.ifunc_relocations().collect::<Vec<_>>();
+    assert!(!ifuncs.is_empty(), "glibc dispatches its string functions");
+    for ifunc in &ifuncs {
+        assert_eq!(ifunc.relocation.symbol_index(), 0);
+        let resolver = ifunc.resolver.expect("rela tables carry the resolver");
+        assert!(library.contains_addr(resolver));
+        // glibc runs its cache info initializer as a resolver storing 0
+        assert!(ifunc.implementation == 0 || library.contains_addr(ifunc.implementation));
+        assert_ne!(resolver, ifunc.implementation);
+    }
+
+    // Internal calls to strlen go through an IRELATIVE slot holding the same implementation dlsym hands out
+    let strlen = ifuncs
+        .iter()
+        .find(|ifunc| ifunc.resolver_symbol == Some(c"strlen"))
+        .expect("strlen is an ifunc");
+    assert_eq!(strlen.implementation, global_symbol(c"strlen"));
+}
+
+#[test]
+fn finds_symbols_by_address() {
+    let module = find_module_by_name("libc.so.6").expect("libc is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let free = global_symbol(c"free");
+    let name = dyn_lib.symbol_at(free).expect("free is exported");
+    assert_eq!(global_symbol(name), free);
+    // Addresses inside a function resolve to it as well
+    let inside = dyn_lib.symbol_at(free + 1).expect("within free");
+    assert_eq!(global_symbol(inside), free);
+    assert!(dyn_lib.symbol_at(0).is_none());
+}
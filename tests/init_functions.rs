@@ -0,0 +1,106 @@
+#![cfg(target_env = "gnu")]
+
+mod common;
+
+use common::build_fixture;
+use plt_rs::{find_module_by_name, main_executable, InitError, InitKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static DESTROYED: AtomicUsize = AtomicUsize::new(0);
+static REPLACED: AtomicUsize = AtomicUsize::new(0);
+static REPLACED_FINI: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn on_destroy() {
+    DESTROYED.fetch_add(1, Ordering::SeqCst);
+}
+
+extern "C" fn replacement_destructor() {
+    REPLACED.fetch_add(1, Ordering::SeqCst);
+}
+
+extern "C" fn replacement_fini() {
+    REPLACED_FINI.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn replaces_destructors_before_unload() {
+    let Some(output) = build_fixture("libinit_fini.so", "init_fini.c", &[]) else {
+        return;
+    };
+
+    let path = std::ffi::CString::new(output.as_os_str().as_encoded_bytes()).unwrap();
+    let handle = unsafe { libc::dlopen(path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load fixture");
+    let lookup = |name: &core::ffi::CStr| unsafe { libc::dlsym(handle, name.as_ptr()) as usize };
+    assert_eq!(unsafe { *(lookup(c"init_fini_state") as *const i32) }, 1);
+    unsafe { *(lookup(c"init_fini_on_destroy") as *mut usize) = on_destroy as *const () as usize };
+
+    // The snapshot keeps the fixture loaded, release it before unloading
+    let fini_replaced = {
+        let module = find_module_by_name("libinit_fini.so").expect("fixture is loaded");
+        let dyn_lib = module.dynamic().expect("can parse");
+        let functions = dyn_lib.init_functions().collect::<Vec<_>>();
+        let constructor = functions
+            .iter()
+            .find(|function| function.symbol == Some(c"init_fini_constructor"))
+            .expect("constructor is listed");
+        assert_eq!(constructor.kind, InitKind::InitArray);
+        assert_eq!(constructor.addr, lookup(c"init_fini_constructor"));
+        let destructor = *functions
+            .iter()
+            .find(|function| function.symbol == Some(c"init_fini_destructor"))
+            .expect("destructor is listed");
+        assert_eq!(destructor.kind, InitKind::FiniArray);
+        assert!(destructor.kind.is_destructor());
+        let previous = dyn_lib
+            .replace_init_function(&destructor, replacement_destructor as *const () as usize)
+            .expect("can replace");
+        assert_eq!(previous, destructor.addr);
+
+        // DT_FINI holds an address relative to the load bias
+        let fini = functions
+            .iter()
+            .find(|function| function.kind == InitKind::Fini);
+        if let Some(fini) = fini {
+            assert!(dyn_lib.library().contains_addr(fini.addr));
+            let previous = dyn_lib
+                .replace_init_function(fini, replacement_fini as *const () as usize)
+                .expect("can replace");
+            assert_eq!(previous, fini.addr);
+        }
+        fini.is_some()
+    };
+
+    unsafe { libc::dlclose(handle) };
+    assert_eq!(DESTROYED.load(Ordering::SeqCst), 0);
+    assert_eq!(REPLACED.load(Ordering::SeqCst), 1);
+    assert_eq!(REPLACED_FINI.load(Ordering::SeqCst), fini_replaced as usize);
+}
+
+#[test]
+fn lists_executable_constructors() {
+    let module = main_executable().expect("main executable");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let library = dyn_lib.library();
+
+    let functions = dyn_lib.init_functions().collect::<Vec<_>>();
+    assert!(functions
+        .iter()
+        .any(|function| function.kind == InitKind::InitArray));
+
+    // A slot the library does not register is never written
+    static FORGED: usize = 0;
+    let mut forged = functions[0];
+    forged.slot = &FORGED as *const usize as usize;
+    assert!(matches!(
+        dyn_lib.replace_init_function(&forged, on_destroy as *const () as usize),
+        Err(InitError::NotInLibrary)
+    ));
+
+    for function in functions {
+        assert!(library.contains_addr(function.slot));
+        if function.addr != 0 && function.addr != usize::MAX {
+            assert!(library.contains_addr(function.addr));
+        }
+    }
+}
@@ -1,5 +1,14 @@
+mod common;
+
+use common::{header, well_formed, Image, DYNAMIC, ET_DYN, HASH, IMAGE_SIZE, PT_DYNAMIC, PT_LOAD};
 use libc::c_void;
-use plt_rs::{collect_modules, DynamicLibrary, RelocationTable};
+use plt_rs::{
+    collect_modules, find_module_by_name, find_module_by_soname, for_each_link_map_entry,
+    for_each_module, main_executable, module_containing, ElfClass, ElfType, Glob, LoadedLibrary,
+    Machine, ModuleKind, ModuleSnapshot, RelocationKind, RelocationTable, SegmentType,
+    SymbolSelector,
+};
+use std::ops::ControlFlow;
 
 /// Make sure we can load all the modules we load ourselves
 /// A simple sanity check, we are not checking the modules contents in any meaningful way.
@@ -8,8 +17,8 @@ use plt_rs::{collect_modules, DynamicLibrary, RelocationTable};
 fn can_load_own_link_map() {
     let entries = collect_modules();
 
-    for entry in entries.into_iter() {
-        if let Ok(dynamic_lib) = DynamicLibrary::initialize(entry) {
+    for entry in entries.iter() {
+        if let Ok(dynamic_lib) = entry.dynamic() {
             let dynamic_symbols = dynamic_lib.symbols().expect("symbols...");
             let string_table = dynamic_lib.string_table();
             if let Some(dyn_relas) = dynamic_lib.addend_relocs() {
@@ -66,34 +75,15 @@ unsafe fn getpid() -> u32 {
     999
 }
 
-/// Finding executable target differs on unix and android
-#[cfg(target_os = "linux")]
-fn find_executable<'a>() -> Option<plt_rs::LoadedLibrary<'a>> {
-    let loaded_modules = collect_modules();
-    loaded_modules.into_iter().next()
-}
-
-/// Finding executable target differs on unix and android
-#[cfg(target_os = "android")]
-fn find_executable<'a>() -> Option<plt_rs::LoadedLibrary<'a>> {
-    let executable = std::env::current_exe().expect("current exe");
-    let file_stem = executable.file_stem()?;
-    let file_stem = file_stem.to_str()?;
-    let loaded_modules = collect_modules();
-    loaded_modules
-        .into_iter()
-        .filter(|lib| lib.name().contains(file_stem))
-        .next()
-}
 #[test]
 fn can_hook_getpid() {
     let my_pid = unsafe { libc::getpid() };
     println!("application pid is {my_pid}");
 
-    let executable_entry = find_executable().expect("can find executable");
+    let executable_entry = main_executable().expect("can find executable");
     println!("successfully identified executable");
 
-    let dyn_lib = DynamicLibrary::initialize(executable_entry).expect("can load");
+    let dyn_lib = executable_entry.dynamic().expect("can load");
     println!("successfully initialied dynamic library for instrumentation");
 
     let target_function = dyn_lib
@@ -132,3 +122,342 @@ fn can_hook_getpid() {
     let get_pid = unsafe { libc::getpid() };
     assert_eq!(get_pid, 999)
 }
+
+/// Every relocation for a symbol is reported with its kind.
+/// Rust emits `-fno-plt` style GLOB_DAT call sites, so hooking has to cover every hookable slot.
+#[test]
+fn can_find_every_getpid_relocation() {
+    let executable_entry = main_executable().expect("can find executable");
+    let dyn_lib = executable_entry.dynamic().expect("can load");
+
+    let relocations = dyn_lib.find_relocations("getpid").collect::<Vec<_>>();
+    assert!(!relocations.is_empty(), "executable should link getpid");
+    for relocation in relocations.iter() {
+        println!("getpid relocation: {relocation:X?}");
+        assert_ne!(relocation.kind(), RelocationKind::Copy);
+    }
+
+    let hookable = dyn_lib.find_hookable_relocations("getpid").count();
+    assert!(hookable > 0, "getpid should be hookable");
+    assert!(dyn_lib
+        .find_hookable_relocations("getpid")
+        .all(|r| r.kind().is_hookable()));
+    assert_eq!(dyn_lib.find_relocations("plt_rs_not_a_symbol").count(), 0);
+}
+
+#[test]
+fn glob_matches_symbol_families() {
+    let glob = Glob::new("pthread_mutex_*");
+    assert!(glob.matches(b"pthread_mutex_lock"));
+    assert!(glob.matches(b"pthread_mutex_"));
+    assert!(!glob.matches(b"pthread_cond_wait"));
+
+    let glob = Glob::new("__*_chk");
+    assert!(glob.matches(b"__memcpy_chk"));
+    assert!(!glob.matches(b"__memcpy_chk_fail"));
+
+    assert!(Glob::new("get?id").matches(b"getpid"));
+    assert!(Glob::new("mem[cm]*").matches(b"memmove"));
+    assert!(!Glob::new("mem[!cm]*").matches(b"memmove"));
+    assert!(Glob::new("str[a-c]*").matches(b"strcmp"));
+    assert!(Glob::new("a\\*b").matches(b"a*b"));
+    assert!(!Glob::new("a\\*b").matches(b"axb"));
+}
+
+/// Selectors apply to the resolved names, and versions, of every relocation table
+#[test]
+fn can_select_getpid_relocations() {
+    let executable_entry = main_executable().expect("can find executable");
+    let dyn_lib = executable_entry.dynamic().expect("can load");
+    let expected = dyn_lib.find_relocations("getpid").count();
+
+    let selector = SymbolSelector::glob("getp[i]d");
+    let selected = dyn_lib.select_relocations(&selector).collect::<Vec<_>>();
+    assert_eq!(selected.len(), expected);
+    assert!(selected.iter().all(|s| s.name == c"getpid"));
+
+    #[cfg(target_env = "gnu")]
+    {
+        let selector = SymbolSelector::glob("getpid@GLIBC_*");
+        assert_eq!(dyn_lib.select_relocations(&selector).count(), expected);
+        let selector = SymbolSelector::exact("getpid").with_version("NOT_A_VERSION");
+        assert_eq!(dyn_lib.select_relocations(&selector).count(), 0);
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn can_select_relocations_by_regex() {
+    let executable_entry = main_executable().expect("can find executable");
+    let dyn_lib = executable_entry.dynamic().expect("can load");
+    let expected = dyn_lib.find_relocations("getpid").count();
+
+    let selector = SymbolSelector::regex("^getp.d$").expect("valid regex");
+    assert_eq!(dyn_lib.select_relocations(&selector).count(), expected);
+}
+
+/// The index must agree with the linear scan for every indexed symbol
+#[test]
+fn index_agrees_with_linear_scan() {
+    let executable_entry = main_executable().expect("can find executable");
+    let dyn_lib = executable_entry.dynamic().expect("can load");
+    let index = dyn_lib.build_index();
+
+    let getpid = index.find("getpid");
+    assert!(!getpid.is_empty(), "executable should link getpid");
+    assert_eq!(getpid.len(), dyn_lib.find_relocations("getpid").count());
+    assert_eq!(
+        index.find_hookable("getpid").count(),
+        dyn_lib.find_hookable_relocations("getpid").count()
+    );
+
+    let version = getpid[0].version.map(|v| v.to_str().expect("utf8 version"));
+    assert_eq!(index.find_versioned("getpid", version).len(), getpid.len());
+
+    for entry in index.entries() {
+        let name = entry.name.to_str().expect("utf8 name");
+        let scanned = dyn_lib.find_relocations(name).collect::<Vec<_>>();
+        assert_eq!(index.find(name).len(), scanned.len());
+        assert!(scanned.contains(&entry.relocation));
+    }
+    assert!(index.find("plt_rs_not_a_symbol").is_empty());
+}
+
+#[test]
+fn visitor_agrees_with_collected_modules() {
+    let collected = collect_modules()
+        .iter()
+        .map(|lib| (lib.addr(), lib.name().into_owned()))
+        .collect::<Vec<_>>();
+
+    let mut visited = Vec::new();
+    let stopped = for_each_module(|lib| {
+        visited.push((lib.addr(), lib.name().into_owned()));
+        ControlFlow::<()>::Continue(())
+    });
+    assert!(stopped.is_none());
+    assert_eq!(visited, collected);
+
+    let first = for_each_module(|lib| ControlFlow::Break(lib.addr()));
+    assert_eq!(first, collected.first().map(|(addr, _)| *addr));
+}
+
+/// Snapshots own everything they expose and keep their module loaded, so they can cross threads
+#[test]
+fn snapshots_can_be_sent_to_workers() {
+    fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+    assert_send_sync_clone::<ModuleSnapshot>();
+
+    let snapshots = collect_modules();
+    assert!(!snapshots.is_empty());
+    assert!(snapshots.iter().all(|snapshot| snapshot.is_pinned()));
+
+    let executable = main_executable().expect("can find executable");
+    let worker = std::thread::spawn(move || {
+        let dyn_lib = executable.dynamic().expect("can load");
+        dyn_lib.find_hookable_relocations("getpid").count()
+    });
+    assert!(worker.join().expect("worker finished") > 0);
+
+    let cloned = snapshots.clone();
+    drop(snapshots);
+    for snapshot in cloned.iter() {
+        let library = snapshot.library().expect("pinned library");
+        assert_eq!(library.addr(), snapshot.addr());
+        assert_eq!(library.name(), snapshot.name());
+    }
+}
+
+#[test]
+fn can_classify_and_find_modules() {
+    let executable = main_executable().expect("can find executable");
+    assert_eq!(executable.kind(), ModuleKind::MainProgram);
+    let snapshots = collect_modules();
+    let main_programs = snapshots
+        .iter()
+        .filter(|m| m.kind() == ModuleKind::MainProgram)
+        .count();
+    assert_eq!(main_programs, 1);
+
+    // our own code lives in the executable, libc's getpid in libc
+    let containing = module_containing(can_classify_and_find_modules as *const () as usize)
+        .expect("executable contains its own code");
+    assert_eq!(containing.addr(), executable.addr());
+    let libc = module_containing(libc::getpid as *const () as usize).expect("libc is loaded");
+    assert_eq!(libc.kind(), ModuleKind::SharedObject);
+
+    let libc_name = libc.name();
+    let file_name = libc_name.rsplit('/').next().expect("libc has a path");
+    let by_name = find_module_by_name(file_name).expect("can find libc by file name");
+    assert_eq!(by_name.addr(), libc.addr());
+    let by_path = find_module_by_name(&libc_name).expect("can find libc by path");
+    assert_eq!(by_path.addr(), libc.addr());
+
+    let libc_dyn = libc.dynamic().expect("can load libc");
+    let soname = libc_dyn.soname().expect("libc has a soname");
+    let by_soname = find_module_by_soname(soname.to_str().expect("utf8 soname"))
+        .expect("can find libc by soname");
+    assert_eq!(by_soname.addr(), libc.addr());
+
+    assert!(module_containing(0).is_none());
+    assert!(find_module_by_name("libplt_rs_not_a_module.so").is_none());
+
+    if unsafe { libc::getauxval(libc::AT_SYSINFO_EHDR) } != 0 {
+        assert!(snapshots.iter().any(|m| m.kind() == ModuleKind::Vdso));
+    }
+    if unsafe { libc::getauxval(libc::AT_BASE) } != 0 {
+        assert!(snapshots
+            .iter()
+            .any(|m| m.kind() == ModuleKind::DynamicLinker));
+    }
+}
+
+/// The main program asks for the dynamic linker we were loaded by, and its segments follow the usual layout
+#[test]
+fn program_headers_are_typed() {
+    let file_name = |path: &str| path.rsplit('/').next().unwrap_or(path).to_owned();
+    let executable = main_executable().expect("main executable");
+    let lib = executable.library().expect("main executable is pinned");
+
+    let interpreter = lib.interpreter_path().expect("interpreter path");
+    let interpreter = file_name(interpreter.to_str().expect("utf8 interpreter path"));
+    if let Some(linker) = collect_modules()
+        .iter()
+        .find(|m| m.kind() == ModuleKind::DynamicLinker)
+    {
+        assert_eq!(file_name(&linker.name()), interpreter);
+    }
+
+    assert!(lib.load_headers().all(|p_h| p_h.is_readable()));
+    assert!(lib.load_headers().any(|p_h| p_h.is_executable()));
+    assert!(lib.load_headers().all(|p_h| {
+        let align = p_h.alignment();
+        align <= 1
+            || (align.is_power_of_two() && p_h.virtual_addr() % align == p_h.offset() % align)
+    }));
+    assert!(lib
+        .program_headers()
+        .filter(|p_h| p_h.segment_type() == SegmentType::GnuStack)
+        .all(|p_h| !p_h.is_executable()));
+
+    let dynamic = lib
+        .program_headers()
+        .find(|p_h| p_h.segment_type() == SegmentType::Dynamic)
+        .expect("dynamic header");
+    let range = dynamic.absolute_range(lib.addr());
+    assert_eq!(range.start, lib.addr() + dynamic.virtual_addr());
+    assert!(lib.contains_addr(range.start) && lib.contains_addr(range.end - 1));
+    assert_eq!(dynamic.header_type(), 2);
+}
+
+/// The elf headers of the modules we run with describe the architecture we were built for
+#[test]
+fn elf_headers_match_the_target() {
+    let expected_class = match cfg!(target_pointer_width = "64") {
+        true => ElfClass::Elf64,
+        false => ElfClass::Elf32,
+    };
+    let expected_machine = if cfg!(target_arch = "x86_64") {
+        Some(Machine::X86_64)
+    } else if cfg!(target_arch = "x86") {
+        Some(Machine::X86)
+    } else if cfg!(target_arch = "aarch64") {
+        Some(Machine::AArch64)
+    } else if cfg!(target_arch = "arm") {
+        Some(Machine::Arm)
+    } else {
+        None
+    };
+
+    for module in collect_modules() {
+        let Some(lib) = module.library() else {
+            continue;
+        };
+        let header = lib.elf_header().expect("elf header");
+        assert_eq!(header.class(), expected_class, "{}", module.name());
+        assert_eq!(header.is_little_endian(), cfg!(target_endian = "little"));
+        if let Some(machine) = expected_machine {
+            assert_eq!(header.machine(), machine, "{}", module.name());
+        }
+        assert_eq!(header.program_header_count(), lib.program_headers().count());
+
+        match module.kind() {
+            ModuleKind::MainProgram => {
+                assert_eq!(header.is_position_independent(), lib.addr() != 0);
+                assert!(lib.contains_addr(lib.addr() + header.entry_point()));
+            }
+            _ => assert_eq!(header.file_type(), ElfType::SharedObject),
+        }
+    }
+}
+
+/// Processor specific segment types only take a meaning from the machine the module was built for
+#[test]
+fn processor_specific_segments_follow_the_module_machine() {
+    const PT_ARM_EXIDX: u32 = 0x70000001;
+    const EM_ARM: u16 = 40;
+    const EM_AARCH64: u16 = 183;
+
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+        header(PT_ARM_EXIDX, HASH, 8),
+    ];
+    let segment_type = |image: &Image| {
+        let lib =
+            unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+        lib.program_headers()
+            .last()
+            .map(|p_h| p_h.segment_type())
+            .expect("three headers")
+    };
+
+    let arm = Image::new(&well_formed()).with_elf_header(ET_DYN, EM_ARM);
+    assert_eq!(segment_type(&arm), SegmentType::ArmExidx);
+    let aarch64 = Image::new(&well_formed()).with_elf_header(ET_DYN, EM_AARCH64);
+    assert_eq!(segment_type(&aarch64), SegmentType::Other(PT_ARM_EXIDX));
+    let headerless = Image::new(&well_formed());
+    assert_eq!(segment_type(&headerless), SegmentType::Other(PT_ARM_EXIDX));
+}
+
+/// Walking r_debug's link map has to report the same modules as `dl_iterate_phdr`
+#[test]
+fn link_map_agrees_with_dl_iterate_phdr() {
+    let describe = |lib: &plt_rs::LoadedLibrary<'_>| {
+        let program_headers = lib
+            .program_headers()
+            .map(|p_h| (p_h.header_type(), p_h.virtual_addr(), p_h.memory_size()))
+            .collect::<Vec<_>>();
+        (lib.addr(), lib.name_cstr().to_owned(), program_headers)
+    };
+
+    let mut iterated = Vec::new();
+    for_each_module(|lib| {
+        iterated.push(describe(&lib));
+        ControlFlow::<()>::Continue(())
+    });
+
+    let mut walked = Vec::new();
+    let mut found_main = false;
+    unsafe {
+        for_each_link_map_entry(|entry| {
+            let dynamic_header = entry
+                .library
+                .program_headers()
+                .find(|p_h| p_h.header_type() == PT_DYNAMIC)
+                .expect("dynamic header");
+            assert_eq!(
+                entry.dynamic_addr,
+                entry.library.addr() + dynamic_header.virtual_addr()
+            );
+            found_main |= entry.library.kind() == ModuleKind::MainProgram;
+            walked.push(describe(&entry.library));
+            ControlFlow::<()>::Continue(())
+        })
+    };
+
+    assert!(found_main);
+    iterated.sort();
+    walked.sort();
+    assert_eq!(iterated, walked);
+}
@@ -0,0 +1,66 @@
+#![cfg(target_env = "gnu")]
+
+use plt_rs::{find_module_by_name, main_executable, BindError, RelocationKind};
+
+/// Built for lazy binding, unlike the test binary, and not linked by it so its PLT is untouched.
+/// Kept in its own test binary, another test calling into it would bind its slots.
+const LIBRARY: &core::ffi::CStr = c"libresolv.so.2";
+
+#[test]
+fn force_resolves_lazily_bound_slots() {
+    if std::env::var_os("LD_BIND_NOW").is_some() {
+        return;
+    }
+
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_LAZY | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load test library");
+    let module = find_module_by_name("libresolv.so.2").expect("libresolv");
+    let dyn_lib = module.dynamic().expect("can parse");
+
+    let free = dyn_lib
+        .find_relocations("free")
+        .find(|reloc| reloc.kind() == RelocationKind::JumpSlot)
+        .expect("libresolv calls free through its plt");
+    assert!(dyn_lib.is_slot_unresolved(&free));
+    assert!(dyn_lib
+        .slot_value(&free)
+        .is_some_and(|value| dyn_lib.library().contains_addr(value)));
+
+    let expected = unsafe { libc::dlsym(libc::RTLD_DEFAULT, c"free".as_ptr()) } as usize;
+    assert_eq!(
+        dyn_lib.force_resolve(&free).expect("free resolves"),
+        expected
+    );
+    assert_eq!(dyn_lib.slot_value(&free), Some(expected));
+    assert!(!dyn_lib.is_slot_unresolved(&free));
+    // Already bound slots are left as they are
+    assert_eq!(dyn_lib.force_resolve(&free).expect("still bound"), expected);
+
+    let bound = dyn_lib.force_resolve_all().expect("every slot resolves");
+    assert!(bound > 0);
+    assert!(dyn_lib
+        .relocations()
+        .all(|reloc| !dyn_lib.is_slot_unresolved(&reloc)));
+    assert_eq!(dyn_lib.force_resolve_all().expect("nothing left"), 0);
+}
+
+/// The test binary is linked with immediate binding, none of its slots wait on the resolver
+#[test]
+fn immediately_bound_modules_have_no_unresolved_slots() {
+    let executable = main_executable().expect("main executable");
+    let dyn_lib = executable.dynamic().expect("can parse");
+    assert!(dyn_lib
+        .relocations()
+        .all(|reloc| !dyn_lib.is_slot_unresolved(&reloc)));
+    assert_eq!(dyn_lib.force_resolve_all().expect("nothing to bind"), 0);
+
+    let glob_dat = dyn_lib
+        .relocations()
+        .find(|reloc| reloc.kind() == RelocationKind::GlobDat);
+    if let Some(glob_dat) = glob_dat {
+        assert!(matches!(
+            dyn_lib.force_resolve(&glob_dat),
+            Err(BindError::NotAJumpSlot(RelocationKind::GlobDat))
+        ));
+    }
+}
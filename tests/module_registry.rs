@@ -0,0 +1,103 @@
+#![cfg(target_env = "gnu")]
+
+use plt_rs::{link_map_generation, ModuleChange, ModuleRegistry};
+
+/// Part of glibc but not linked by the test binary, so it can be loaded and unloaded at will.
+/// Kept in its own test binary, a snapshot pinned by a concurrently running test would keep it loaded.
+const LIBRARY: &core::ffi::CStr = c"libresolv.so.2";
+
+fn is_cached(registry: &ModuleRegistry) -> bool {
+    registry
+        .modules()
+        .iter()
+        .any(|module| module.name().ends_with("libresolv.so.2"))
+}
+
+#[test]
+fn registry_tracks_loads_and_unloads() {
+    assert!(link_map_generation().is_some());
+
+    let mut registry = ModuleRegistry::new();
+    let initial = registry.generation();
+    assert!(!is_cached(&registry));
+    assert!(registry.changes_since(0).count() >= registry.modules().len());
+    assert!(!registry.refresh(), "nothing was loaded yet");
+    assert_eq!(registry.generation(), initial);
+
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load test library");
+
+    assert!(registry.refresh());
+    assert!(is_cached(&registry));
+    assert!(registry.generation() > initial);
+    let added = registry.changes_since(initial).collect::<Vec<_>>();
+    assert!(added.iter().any(|event| event.change == ModuleChange::Added
+        && event.module.name().ends_with("libresolv.so.2")));
+    let loaded = registry.generation();
+
+    let module = registry
+        .modules()
+        .iter()
+        .find(|module| module.name().ends_with("libresolv.so.2"))
+        .expect("library is cached");
+    assert!(!module.is_pinned());
+    assert!(module.is_loaded());
+    {
+        let pinned = module.pinned().expect("library is loaded");
+        let parsed = pinned.dynamic().expect("can parse");
+        let cached = registry.dynamic(&pinned).expect("tables are cached");
+        assert_eq!(
+            cached.dyn_section().entries().as_ptr(),
+            parsed.dyn_section().entries().as_ptr()
+        );
+        assert!(cached.try_find_function("free").is_some());
+        assert!(matches!(
+            registry.dynamic(module),
+            Err(plt_rs::DynamicError::Unpinned)
+        ));
+    }
+
+    unsafe { libc::dlclose(handle) };
+
+    assert!(registry.refresh());
+    assert!(!is_cached(&registry));
+    let removed = registry.changes_since(loaded).collect::<Vec<_>>();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(removed[0].change, ModuleChange::Removed);
+    assert!(!removed[0].module.is_loaded());
+    assert!(removed[0].module.pinned().is_none());
+
+    registry.discard_changes_through(loaded);
+    assert_eq!(registry.changes_since(0).count(), 1);
+    registry.discard_changes_through(registry.generation());
+    assert_eq!(registry.changes_since(0).count(), 0);
+
+    // Unloaded and loaded again between refreshes, usually at the same address
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load test library");
+    assert!(registry.refresh());
+    let before_reload = registry.generation();
+    unsafe { libc::dlclose(handle) };
+    let handle = unsafe { libc::dlopen(LIBRARY.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load test library");
+
+    assert!(registry.refresh());
+    let reloaded = registry
+        .changes_since(before_reload)
+        .filter(|event| event.module.name().ends_with("libresolv.so.2"))
+        .map(|event| event.change)
+        .collect::<Vec<_>>();
+    assert_eq!(reloaded, [ModuleChange::Removed, ModuleChange::Added]);
+    {
+        let module = registry
+            .modules()
+            .iter()
+            .find(|module| module.name().ends_with("libresolv.so.2"))
+            .expect("library is cached");
+        let pinned = module.pinned().expect("library is loaded");
+        assert!(registry
+            .dynamic(&pinned)
+            .is_ok_and(|dyn_lib| dyn_lib.try_find_function("free").is_some()));
+    }
+    unsafe { libc::dlclose(handle) };
+}
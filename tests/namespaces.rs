@@ -0,0 +1,79 @@
+#![cfg(target_env = "gnu")]
+
+use plt_rs::{
+    collect_modules, collect_modules_in, for_each_link_map_entry, main_executable, ModuleKind,
+    ModuleSnapshot, Namespace,
+};
+use std::ops::ControlFlow;
+
+/// Loads a second copy of libc along with it, kept in its own test binary so the other tests never see the namespace
+const LIBRARY: &core::ffi::CStr = c"libresolv.so.2";
+
+fn find<'a>(modules: &'a [ModuleSnapshot], file_name: &str) -> Option<&'a ModuleSnapshot> {
+    modules
+        .iter()
+        .find(|module| module.name().rsplit('/').next() == Some(file_name))
+}
+
+#[test]
+fn can_tell_namespaces_apart() {
+    let executable = main_executable().expect("main executable");
+    assert_eq!(executable.namespace(), Some(Namespace::BASE));
+    assert_eq!(
+        Namespace::containing(libc::getpid as *const () as usize),
+        Some(Namespace::BASE)
+    );
+
+    let handle = unsafe { libc::dlmopen(libc::LM_ID_NEWLM, LIBRARY.as_ptr(), libc::RTLD_NOW) };
+    assert!(!handle.is_null(), "unable to load test library");
+    let mut id: libc::Lmid_t = 0;
+    let res = unsafe {
+        libc::dlinfo(
+            handle,
+            libc::RTLD_DI_LMID,
+            &mut id as *mut libc::Lmid_t as *mut libc::c_void,
+        )
+    };
+    assert_eq!(res, 0);
+    let namespace = Namespace::from_id(id);
+    assert_ne!(namespace, Namespace::BASE);
+
+    let base_modules = collect_modules_in(Namespace::BASE);
+    let new_modules = collect_modules_in(namespace);
+    assert!(find(&base_modules, "libresolv.so.2").is_none());
+    let resolv = find(&new_modules, "libresolv.so.2").expect("libresolv in new namespace");
+    assert!(resolv.is_pinned());
+    assert!(resolv.dynamic().is_ok());
+
+    // Each namespace holds its own copy of libc
+    let base_libc = find(&base_modules, "libc.so.6").expect("libc in base namespace");
+    let new_libc = find(&new_modules, "libc.so.6").expect("libc in new namespace");
+    assert_ne!(base_libc.addr(), new_libc.addr());
+    assert!(new_libc.is_pinned());
+    assert_eq!(new_libc.namespace(), Some(namespace));
+    assert!(new_libc.dynamic().is_ok());
+
+    // dl_iterate_phdr only reports our own namespace
+    let own = collect_modules();
+    assert!(own
+        .iter()
+        .all(|module| module.namespace() == Some(Namespace::BASE)));
+    assert!(own
+        .iter()
+        .all(|module| base_modules.iter().any(|base| base.addr() == module.addr())));
+
+    let mut walked = 0;
+    unsafe {
+        for_each_link_map_entry(|entry| {
+            if entry.namespace == namespace {
+                walked += 1;
+                // The dynamic linker is shared by all namespaces, only its base namespace entry is found by address
+                if entry.library.kind() != ModuleKind::DynamicLinker {
+                    assert_eq!(entry.library.namespace(), Some(namespace));
+                }
+            }
+            ControlFlow::<()>::Continue(())
+        })
+    };
+    assert_eq!(walked, new_modules.len());
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use common::{elf, header_at, ET_DYN, PT_LOAD};
+use plt_rs::{main_executable, module_containing, GnuProperty, LoadedLibrary, NoteKind};
+
+const PT_NOTE: u32 = 4;
+const EM_X86_64: u16 = 62;
+const EM_AARCH64: u16 = 183;
+
+const NOTES: usize = 64;
+const IMAGE_SIZE: usize = 256;
+
+/// A hand made module holding an elf header followed by a single PT_NOTE segment
+#[repr(C, align(16))]
+struct NoteImage {
+    bytes: [u8; IMAGE_SIZE],
+    len: usize,
+}
+
+impl NoteImage {
+    fn new(e_machine: u16) -> Box<Self> {
+        let mut image = Box::new(NoteImage {
+            bytes: [0; IMAGE_SIZE],
+            len: NOTES,
+        });
+        let mut e_ident = [0; 16];
+        e_ident[..4].copy_from_slice(b"\x7fELF");
+        let header = elf::FileHeader {
+            e_ident,
+            e_type: ET_DYN,
+            e_machine,
+            e_version: 1,
+            e_entry: 0,
+            e_phoff: 0,
+            e_shoff: 0,
+            e_flags: 0,
+            e_ehsize: size_of::<elf::FileHeader>() as _,
+            e_phentsize: size_of::<elf::ProgramHeader>() as _,
+            e_phnum: 0,
+            e_shentsize: 0,
+            e_shnum: 0,
+            e_shstrndx: 0,
+        };
+        unsafe {
+            image
+                .bytes
+                .as_mut_ptr()
+                .cast::<elf::FileHeader>()
+                .write(header)
+        };
+        image
+    }
+
+    /// Append a note, padding its name and descriptor to `align`
+    fn note(&mut self, name: &[u8], note_type: u32, desc: &[u8], align: usize) {
+        let words = [name.len() as u32, desc.len() as u32, note_type];
+        for word in words {
+            self.push(&word.to_ne_bytes(), 1);
+        }
+        self.push(name, align);
+        self.push(desc, align);
+    }
+
+    fn push(&mut self, bytes: &[u8], align: usize) {
+        self.bytes[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len = (self.len + bytes.len()).next_multiple_of(align);
+    }
+
+    fn headers(&self, align: usize) -> [elf::ProgramHeader; 2] {
+        let mut note = header_at(PT_NOTE, NOTES, NOTES, self.len - NOTES);
+        note.p_align = align as _;
+        [header_at(PT_LOAD, 0, 0, IMAGE_SIZE), note]
+    }
+
+    fn addr(&self) -> usize {
+        self.bytes.as_ptr() as usize
+    }
+}
+
+/// A GNU property descriptor holding a single feature word
+fn feature_property(pr_type: u32, features: u32) -> Vec<u8> {
+    let mut desc = Vec::new();
+    for word in [pr_type, size_of::<u32>() as u32, features] {
+        desc.extend_from_slice(&word.to_ne_bytes());
+    }
+    desc.resize(desc.len().next_multiple_of(size_of::<usize>()), 0);
+    desc
+}
+
+#[test]
+fn decodes_gnu_and_android_notes() {
+    let build_id = [0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x03, 0x04];
+    let abi_tag = [0u32, 3, 2, 0].map(u32::to_ne_bytes).concat();
+    let mut image = NoteImage::new(EM_X86_64);
+    image.note(b"GNU\0", 3, &build_id, 4);
+    image.note(b"GNU\0", 1, &abi_tag, 4);
+    image.note(b"Android\0", 1, &30u32.to_ne_bytes(), 4);
+    image.note(b"Vendor\0", 7, b"raw", 4);
+
+    let program_headers = image.headers(4);
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    assert_eq!(lib.build_id(), Some(&build_id[..]));
+
+    let notes = lib.notes().collect::<Vec<_>>();
+    assert_eq!(notes.len(), 4);
+    assert!(matches!(notes[0].kind(), NoteKind::GnuBuildId(id) if id == build_id));
+    match notes[1].kind() {
+        NoteKind::GnuAbiTag(tag) => assert_eq!((tag.os, tag.major, tag.minor), (0, 3, 2)),
+        kind => panic!("unexpected {kind:?}"),
+    }
+    assert!(matches!(
+        notes[2].kind(),
+        NoteKind::AndroidIdent { api_level: 30 }
+    ));
+    assert!(matches!(notes[3].kind(), NoteKind::Other));
+    assert_eq!(notes[3].name(), b"Vendor");
+    assert_eq!(notes[3].note_type(), 7);
+    assert_eq!(notes[3].desc(), b"raw");
+}
+
+#[test]
+fn decodes_gnu_properties_for_the_module_machine() {
+    let properties = [
+        feature_property(0xc0000002, 0b11),
+        feature_property(0xc0000000, 0b01),
+    ]
+    .concat();
+    let align = size_of::<usize>();
+
+    let mut image = NoteImage::new(EM_X86_64);
+    image.note(b"GNU\0", 5, &properties, align);
+    let program_headers = image.headers(align);
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let Some(NoteKind::GnuProperties(decoded)) = lib.notes().next().map(|note| note.kind()) else {
+        panic!("expected a property note");
+    };
+    let decoded = decoded.collect::<Vec<_>>();
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(
+        decoded[0],
+        GnuProperty::X86Features {
+            ibt: true,
+            shstk: true
+        }
+    );
+    assert!(matches!(
+        decoded[1],
+        GnuProperty::Other {
+            pr_type: 0xc0000000,
+            ..
+        }
+    ));
+    assert_eq!(lib.build_id(), None);
+
+    let mut image = NoteImage::new(EM_AARCH64);
+    image.note(b"GNU\0", 5, &properties, align);
+    let program_headers = image.headers(align);
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let Some(NoteKind::GnuProperties(decoded)) = lib.notes().next().map(|note| note.kind()) else {
+        panic!("expected a property note");
+    };
+    let decoded = decoded.collect::<Vec<_>>();
+    assert!(matches!(decoded[0], GnuProperty::Other { .. }));
+    assert_eq!(
+        decoded[1],
+        GnuProperty::Aarch64Features {
+            bti: true,
+            pac: false
+        }
+    );
+}
+
+#[test]
+fn truncated_notes_end_the_segment() {
+    let mut image = NoteImage::new(EM_X86_64);
+    image.note(b"GNU\0", 3, &[1, 2, 3, 4], 4);
+    // Claims a descriptor running past the end of the segment
+    image.push(&[4u32, 0x1000, 3].map(u32::to_ne_bytes).concat(), 4);
+    image.push(b"GNU\0", 4);
+
+    let program_headers = image.headers(4);
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    assert_eq!(lib.notes().count(), 1);
+}
+
+/// The build ids read from memory have to be the ones recorded in the files on disk
+#[test]
+fn build_ids_match_the_files() {
+    let libc = module_containing(libc::getpid as *const () as usize).expect("libc");
+    let executable = main_executable().expect("main executable");
+    for module in [libc, executable] {
+        let lib = module.library().expect("pinned");
+        let Some(build_id) = lib.build_id() else {
+            continue;
+        };
+        assert!(build_id.len() >= 8);
+
+        let path = match &*module.name() {
+            "" => std::env::current_exe().expect("current exe"),
+            name => name.into(),
+        };
+        let file = std::fs::read(&path).expect("module file");
+        assert!(file.windows(build_id.len()).any(|bytes| bytes == build_id));
+    }
+}
+
+#[test]
+#[cfg(target_env = "gnu")]
+fn libc_has_an_abi_tag() {
+    let libc = module_containing(libc::getpid as *const () as usize).expect("libc");
+    let lib = libc.library().expect("pinned");
+    let tag = lib
+        .notes()
+        .find_map(|note| match note.kind() {
+            NoteKind::GnuAbiTag(tag) => Some(tag),
+            _ => None,
+        })
+        .expect("abi tag");
+    assert_eq!(tag.os, 0);
+    assert!(tag.major >= 2);
+}
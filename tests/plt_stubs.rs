@@ -0,0 +1,115 @@
+#![cfg(all(
+    target_env = "gnu",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+
+mod common;
+
+use common::build_fixture;
+use plt_rs::{collect_modules, RelocationKind};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+/// Offsets of the `<name@plt>` stubs `objdump` disassembles, None if objdump is not available
+fn objdump_stubs(path: &Path) -> Option<HashMap<String, usize>> {
+    let output = Command::new("objdump")
+        .args(["-d", "--no-show-raw-insn"])
+        .arg(path)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // Labels look like `0000000000001030 <free@plt>:`
+    let stubs = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (addr, label) = line.strip_suffix(">:")?.split_once(" <")?;
+            let name = label.strip_suffix("@plt")?;
+            Some((name.to_owned(), usize::from_str_radix(addr, 16).ok()?))
+        })
+        .collect();
+    Some(stubs)
+}
+
+/// Load the library at `path` and check every JUMP_SLOT's stub against objdump
+fn check_against_objdump(path: &Path) {
+    let Some(expected) = objdump_stubs(path) else {
+        eprintln!("skipping: objdump is not available to check {path:?} against");
+        return;
+    };
+    let path_cstr = std::ffi::CString::new(path.as_os_str().as_encoded_bytes()).unwrap();
+    let handle = unsafe { libc::dlopen(path_cstr.as_ptr(), libc::RTLD_LAZY | libc::RTLD_LOCAL) };
+    assert!(!handle.is_null(), "unable to load {path:?}");
+
+    let file_name = path.file_name().unwrap().to_str().unwrap();
+    let module = plt_rs::find_module_by_name(file_name).expect("fixture is loaded");
+    let dyn_lib = module.dynamic().expect("can parse");
+    let base = dyn_lib.library().addr();
+    let symbols = dyn_lib.symbols().expect("has symbols");
+
+    let mut checked = 0;
+    for relocation in dyn_lib.relocations() {
+        if relocation.kind() != RelocationKind::JumpSlot {
+            continue;
+        }
+        let name = symbols
+            .resolve_name(relocation.symbol_index(), dyn_lib.string_table())
+            .expect("named import");
+        let stub = dyn_lib
+            .plt_stub_addr(&relocation)
+            .unwrap_or_else(|| panic!("no stub found for `{name}`"));
+        assert_eq!(
+            Some(&(stub - base)),
+            expected.get(name.as_ref()),
+            "stub of `{name}` in {file_name}"
+        );
+        checked += 1;
+    }
+    assert!(
+        checked >= 4,
+        "fixture calls at least malloc, strlen, puts and free"
+    );
+    assert_eq!(dyn_lib.plt_stubs().count(), checked);
+
+    unsafe { libc::dlclose(handle) };
+}
+
+#[test]
+fn plt_stubs_match_objdump() {
+    if let Some(path) = build_fixture("libplt_plain.so", "plt_calls.c", &["-fcf-protection=none"]) {
+        check_against_objdump(&path);
+    }
+}
+
+#[test]
+#[cfg(target_arch = "x86_64")]
+fn ibt_plt_stubs_match_objdump() {
+    let flags = ["-fcf-protection=full", "-Wl,-z,ibt"];
+    if let Some(path) = build_fixture("libplt_ibt.so", "plt_calls.c", &flags) {
+        check_against_objdump(&path);
+    }
+}
+
+#[test]
+#[cfg(target_arch = "aarch64")]
+fn bti_plt_stubs_match_objdump() {
+    let flags = ["-mbranch-protection=standard", "-Wl,-z,force-bti"];
+    if let Some(path) = build_fixture("libplt_bti.so", "plt_calls.c", &flags) {
+        check_against_objdump(&path);
+    }
+}
+
+#[test]
+fn loaded_modules_have_consistent_stubs() {
+    for module in collect_modules() {
+        let Ok(dyn_lib) = module.dynamic() else {
+            continue;
+        };
+        for stub in dyn_lib.plt_stubs() {
+            assert_eq!(stub.relocation.kind(), RelocationKind::JumpSlot);
+            assert!(dyn_lib.library().contains_addr(stub.addr));
+        }
+    }
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use common::*;
+use plt_rs::{for_each_module, DynamicLibrary, ElfType, LoadedLibrary, ModuleKind};
+use std::ops::ControlFlow;
+
+/// Where `modules_mapped_below_their_vaddr` links the image, relative to where it is actually mapped
+const LINK_OFFSET: usize = 0x1000_0000;
+
+/// Parse `lib` and check its tables were found where the image holds them
+fn assert_tables_found(lib: LoadedLibrary<'_>, image: &Image) {
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+    let symbols = dyn_lib.symbols().expect("symbols");
+    assert_eq!(symbols.entries().as_ptr() as usize, image.addr() + SYMTAB);
+    assert_eq!(symbols.entries().len(), 2);
+    assert_eq!(
+        symbols.resolve_cstr(1, dyn_lib.string_table()),
+        Some(c"getpid")
+    );
+}
+
+/// Check every table the dynamic section points at lies within the module
+fn assert_tables_within(dyn_lib: &DynamicLibrary<'_>) {
+    let lib = dyn_lib.library();
+    let string_table = dyn_lib.string_table().read_bytes_at(0).expect("strings");
+    assert!(lib.contains_addr(string_table.as_ptr() as usize));
+    if let Some(symbols) = dyn_lib.symbols() {
+        assert!(lib.contains_addr(symbols.entries().as_ptr() as usize));
+    }
+    if let Some(relocs) = dyn_lib.addend_relocs() {
+        assert!(lib.contains_addr(relocs.entries().as_ptr() as usize));
+    }
+    if let Some(relocs) = dyn_lib.relocs() {
+        assert!(lib.contains_addr(relocs.entries().as_ptr() as usize));
+    }
+}
+
+/// musl, bionic and the vDSO leave the dynamic section's addresses relative to the load bias
+#[test]
+fn unrelocated_values_are_rebased() {
+    let image = Image::new(&well_formed()).with_elf_type(ET_DYN);
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    assert_tables_found(lib, &image);
+}
+
+/// glibc relocates the dynamic section's addresses in place
+#[test]
+fn relocated_values_are_kept() {
+    let mut image = Image::new(&[]).with_elf_type(ET_DYN);
+    image.write_dynamic(&well_formed_at(image.addr()));
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    assert_tables_found(lib, &image);
+}
+
+/// A load bias wrapping below zero, whether or not the values were relocated
+#[test]
+fn modules_mapped_below_their_vaddr() {
+    let mut image = Image::new(&[]).with_elf_type(ET_DYN);
+    let vaddr = image.addr().wrapping_add(LINK_OFFSET);
+    let bias = image.addr().wrapping_sub(vaddr);
+    let program_headers = [
+        header_at(PT_LOAD, 0, vaddr, IMAGE_SIZE),
+        header_at(PT_DYNAMIC, DYNAMIC, vaddr + DYNAMIC, HASH - DYNAMIC),
+    ];
+
+    for start in [vaddr, image.addr()] {
+        image.write_dynamic(&well_formed_at(start));
+        let lib = unsafe { LoadedLibrary::from_raw_parts(bias, c"synthetic", &program_headers) };
+        assert_tables_found(lib, &image);
+    }
+}
+
+/// Non position independent executables are loaded at a base of 0, with every address absolute
+#[test]
+fn executables_are_not_rebased() {
+    let mut image = Image::new(&[]).with_elf_type(ET_EXEC);
+    image.write_dynamic(&well_formed_at(image.addr()));
+    let program_headers = [
+        header_at(PT_LOAD, 0, image.addr(), IMAGE_SIZE),
+        header_at(PT_DYNAMIC, DYNAMIC, image.addr() + DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib = unsafe { LoadedLibrary::from_raw_parts(0, c"synthetic", &program_headers) };
+    let header = lib.elf_header().expect("elf header");
+    assert_eq!(header.file_type(), ElfType::Executable);
+    assert!(!header.is_position_independent());
+    assert_tables_found(lib, &image);
+}
+
+/// The test executable is position independent, loaded away from its link address
+#[test]
+fn position_independent_main_program() {
+    for_each_module(|lib| match lib.kind() {
+        ModuleKind::MainProgram => {
+            let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+            assert_tables_within(&dyn_lib);
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .expect("main program");
+}
+
+/// The kernel maps the vDSO, no dynamic linker ever relocates its dynamic section
+#[test]
+fn vdso_tables_are_rebased() {
+    if unsafe { libc::getauxval(libc::AT_SYSINFO_EHDR) } == 0 {
+        return;
+    }
+
+    for_each_module(|lib| match lib.kind() {
+        ModuleKind::Vdso => {
+            let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+            assert_tables_within(&dyn_lib);
+            let symbols = dyn_lib.symbols().expect("symbols");
+            let exports_clock = (0..symbols.entries().len()).any(|index| {
+                symbols
+                    .resolve_cstr(index, dyn_lib.string_table())
+                    .and_then(|name| name.to_str().ok())
+                    .is_some_and(|name| name.contains("clock_gettime"))
+            });
+            assert!(exports_clock);
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .expect("vDSO");
+}
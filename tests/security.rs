@@ -0,0 +1,129 @@
+mod common;
+
+use common::*;
+use plt_rs::{
+    main_executable, module_containing, ControlFlowProtection, DynamicLibrary, LoadedLibrary, Relro,
+};
+
+const DT_RUNPATH: usize = 29;
+const DT_FLAGS: usize = 30;
+const DT_FLAGS_1: usize = 0x6ffffffb;
+const DF_TEXTREL: usize = 0x4;
+const DF_1_NOW: usize = 0x1;
+
+const PT_GNU_STACK: u32 = 0x6474e551;
+const PT_GNU_RELRO: u32 = 0x6474e552;
+
+/// A synthetic module importing `__stack_chk_fail` and `__printf_chk`, with a DT_RUNPATH of `/opt/lib`
+fn hardened_image(extra: &[(usize, usize)]) -> Box<Image> {
+    let mut dynamic = extra.to_vec();
+    dynamic.extend(well_formed());
+    let strings = b"\0__stack_chk_fail\0__printf_chk\0/opt/lib\0";
+    dynamic
+        .iter_mut()
+        .filter(|(tag, _)| *tag == DT_STRSZ)
+        .for_each(|(_, size)| *size = strings.len());
+    dynamic
+        .iter_mut()
+        .filter(|(tag, _)| *tag == DT_RUNPATH)
+        .for_each(|(_, offset)| *offset = 31);
+
+    let mut image = Image::new(&dynamic).with_elf_type(ET_DYN);
+    image.put(HASH + size_of::<u32>(), 3u32);
+    image.put(
+        SYMTAB + 2 * size_of::<elf::DynSym>(),
+        elf::DynSym {
+            st_name: 18,
+            st_info: 0x12,
+            st_other: 0,
+            st_shndx: 0,
+            st_value: 0,
+            st_size: 0,
+        },
+    );
+    for (index, byte) in strings.iter().enumerate() {
+        image.put(STRTAB + index, *byte);
+    }
+    image
+}
+
+#[test]
+fn reports_synthetic_hardening() {
+    let image = hardened_image(&[(DT_FLAGS_1, DF_1_NOW), (DT_RUNPATH, 0)]);
+    let mut stack = header(PT_GNU_STACK, 0, 0);
+    stack.p_flags = 6;
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+        header(PT_GNU_RELRO, 0, HASH),
+        stack,
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+    let report = dyn_lib.security_report();
+
+    assert_eq!(report.relro, Relro::Full);
+    assert!(report.position_independent);
+    assert!(report.nx_stack);
+    assert!(!report.text_relocations);
+    assert!(report.stack_protector);
+    assert_eq!(report.fortified_imports, 1);
+    assert_eq!(report.rpath, None);
+    assert_eq!(report.runpath, Some(c"/opt/lib"));
+    assert_eq!(report.control_flow, ControlFlowProtection::default());
+}
+
+#[test]
+fn reports_missing_hardening() {
+    let image = hardened_image(&[(DT_FLAGS, DF_TEXTREL)]);
+    let mut stack = header(PT_GNU_STACK, 0, 0);
+    stack.p_flags = 7;
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+        stack,
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+    let report = dyn_lib.security_report();
+
+    assert_eq!(report.relro, Relro::None);
+    assert!(!report.nx_stack);
+    assert!(report.text_relocations);
+    assert_eq!(report.runpath, None);
+
+    // Partial RELRO without immediate binding
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+        header(PT_GNU_RELRO, 0, HASH),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+    let report = dyn_lib.security_report();
+    assert_eq!(report.relro, Relro::Partial);
+    // No PT_GNU_STACK at all leaves the stack executable
+    assert!(!report.nx_stack);
+}
+
+/// The distribution builds the libraries we load with the usual hardening
+#[test]
+fn reports_loaded_module_hardening() {
+    let libc = module_containing(libc::getpid as *const () as usize).expect("libc");
+    let dyn_lib = libc.dynamic().expect("can parse");
+    let report = dyn_lib.security_report();
+    assert_ne!(report.relro, Relro::None);
+    assert!(report.position_independent);
+    assert!(report.nx_stack);
+    assert!(!report.text_relocations);
+
+    let executable = main_executable().expect("main executable");
+    let dyn_lib = executable.dynamic().expect("can parse");
+    let report = dyn_lib.security_report();
+    assert_eq!(report.position_independent, executable.addr() != 0);
+    assert!(report.nx_stack);
+    assert!(!report.text_relocations);
+}
@@ -0,0 +1,177 @@
+use std::ops::ControlFlow;
+
+use plt_rs::{
+    for_each_module, DynamicError, DynamicLibrary, DynamicSectionType, LoadedLibrary, ParseWarning,
+};
+
+mod common;
+
+use common::*;
+
+/// Parse `image` with its dynamic section at `dynamic_vaddr`, holding `dynamic_size` bytes
+fn parse(image: &Image, dynamic_vaddr: usize, dynamic_size: usize) -> Result<(), DynamicError> {
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, dynamic_vaddr, dynamic_size),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    DynamicLibrary::initialize(lib).map(|_| ())
+}
+
+#[test]
+fn well_formed_module_parses() {
+    let image = Image::new(&well_formed());
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+
+    let symbols = dyn_lib.symbols().expect("symbols");
+    assert_eq!(symbols.entries().len(), 2);
+    assert_eq!(
+        symbols.resolve_cstr(1, dyn_lib.string_table()),
+        Some(c"getpid")
+    );
+    assert_eq!(symbols.resolve_cstr(2, dyn_lib.string_table()), None);
+}
+
+#[test]
+fn unexpected_entry_sizes_are_errors() {
+    let mut dynamic = well_formed();
+    dynamic[2] = (DT_SYMENT, 3);
+    let image = Image::new(&dynamic);
+    assert!(matches!(
+        parse(&image, DYNAMIC, HASH - DYNAMIC),
+        Err(DynamicError::EntrySize(DynamicSectionType::DT_SYMENT, 3))
+    ));
+}
+
+#[test]
+fn tables_outside_the_segments_are_errors() {
+    let mut dynamic = well_formed();
+    dynamic[4] = (DT_STRSZ, IMAGE_SIZE);
+    let image = Image::new(&dynamic);
+    assert!(matches!(
+        parse(&image, DYNAMIC, HASH - DYNAMIC),
+        Err(DynamicError::OutOfBounds(DynamicSectionType::DT_STRTAB))
+    ));
+
+    // A symbol count running past the end of the image
+    let mut image = Image::new(&well_formed());
+    image.put(HASH + size_of::<u32>(), 1000u32);
+    assert!(matches!(
+        parse(&image, DYNAMIC, HASH - DYNAMIC),
+        Err(DynamicError::OutOfBounds(DynamicSectionType::DT_SYMTAB))
+    ));
+}
+
+#[test]
+fn dynamic_section_outside_the_segments_is_an_error() {
+    let image = Image::new(&well_formed());
+    assert!(matches!(
+        parse(&image, IMAGE_SIZE, HASH - DYNAMIC),
+        Err(DynamicError::DynamicSectionBounds)
+    ));
+}
+
+#[test]
+fn dynamic_section_walk_stops_at_the_segment_end() {
+    // No DT_NULL within PT_DYNAMIC, the DT_SYMTAB entry following it must not be found
+    let mut dynamic = well_formed();
+    dynamic.swap(0, 3);
+    dynamic.swap(1, 4);
+    let image = Image::new(&dynamic);
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, 2 * size_of::<elf::DynEntry>()),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let dyn_lib = DynamicLibrary::initialize(lib).expect("can parse");
+    assert!(dyn_lib.symbols().is_none());
+}
+
+#[test]
+fn lenient_parsing_skips_malformed_tables() {
+    // DT_RELA without the DT_RELAENT it depends on
+    let mut dynamic = well_formed();
+    dynamic.insert(0, (DT_RELA, SYMTAB));
+    dynamic.insert(1, (DT_RELASZ, size_of::<elf::DynRela>()));
+    let image = Image::new(&dynamic);
+    assert!(matches!(
+        parse(&image, DYNAMIC, HASH - DYNAMIC),
+        Err(DynamicError::DependentSection(
+            DynamicSectionType::DT_RELA,
+            DynamicSectionType::DT_RELAENT
+        ))
+    ));
+
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+    let (dyn_lib, warnings) = DynamicLibrary::initialize_with_warnings(lib).expect("can parse");
+    assert!(dyn_lib.addend_relocs().is_none());
+    let symbols = dyn_lib.symbols().expect("symbols");
+    assert_eq!(
+        symbols.resolve_cstr(1, dyn_lib.string_table()),
+        Some(c"getpid")
+    );
+    assert!(matches!(
+        warnings.as_slice(),
+        [ParseWarning {
+            table: DynamicSectionType::DT_RELA,
+            error: DynamicError::DependentSection(
+                DynamicSectionType::DT_RELA,
+                DynamicSectionType::DT_RELAENT
+            ),
+        }]
+    ));
+}
+
+#[test]
+fn lenient_parsing_substitutes_a_missing_string_table() {
+    let mut dynamic = well_formed();
+    dynamic[4] = (DT_STRSZ, IMAGE_SIZE);
+    let image = Image::new(&dynamic);
+    let program_headers = [
+        header(PT_LOAD, 0, IMAGE_SIZE),
+        header(PT_DYNAMIC, DYNAMIC, HASH - DYNAMIC),
+    ];
+    let lib =
+        unsafe { LoadedLibrary::from_raw_parts(image.addr(), c"synthetic", &program_headers) };
+
+    let mut warnings = Vec::new();
+    let dyn_lib = DynamicLibrary::initialize_lenient(lib, |warning| warnings.push(warning.table))
+        .expect("can parse");
+    assert_eq!(warnings, [DynamicSectionType::DT_STRTAB]);
+    assert_eq!(dyn_lib.string_table().total_size(), 0);
+    let symbols = dyn_lib.symbols().expect("symbols");
+    assert_eq!(symbols.entries().len(), 2);
+    assert_eq!(symbols.resolve_cstr(1, dyn_lib.string_table()), None);
+}
+
+/// One odd module must never take the process down, but every module we load ourselves is well formed
+#[test]
+fn every_loaded_module_parses() {
+    let mut count = 0;
+    for_each_module(|lib| {
+        let name = lib.name_cstr().to_owned();
+        match DynamicLibrary::initialize_with_warnings(lib.clone()) {
+            Ok((_, warnings)) => assert!(warnings.is_empty(), "{name:?} warned {warnings:?}"),
+            Err(err) => panic!("{name:?} failed to parse leniently: {err}"),
+        }
+        if let Err(err) = DynamicLibrary::initialize(lib) {
+            panic!("{name:?} failed to parse: {err}");
+        }
+        count += 1;
+        ControlFlow::<()>::Continue(())
+    });
+    assert!(count > 0);
+}